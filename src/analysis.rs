@@ -0,0 +1,73 @@
+use crate::game::Game;
+use crate::solver::{entropy, expected_left};
+
+/// How one guess did against the candidates left when it was made.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GuessAnalysis {
+    pub guess: String,
+    pub before: usize, // candidates before the guess
+    pub after: usize, // candidates its feedback left
+    pub bits: f64, // information its feedback gave
+    pub expected_bits: f64, // information it was expected to give
+    pub expected: f64, // candidates it was expected to leave
+    pub best: String, // the recommendation at that point
+    pub best_expected: f64, // candidates the recommendation was expected to leave
+}
+
+impl GuessAnalysis {
+    /// The candidates the recommendation was expected to leave over those the guess was; 1 is as good, above 1 better.
+    pub fn skill(&self) -> f64 {
+        if self.expected > 0.0 {
+            self.best_expected / self.expected
+        } else {
+            1.0
+        }
+    }
+
+    /// Bits the feedback gave beyond those expected, positive when it was kinder than average.
+    pub fn luck(&self) -> f64 {
+        self.bits - self.expected_bits
+    }
+}
+
+/// Every guess of `game` graded against `get_hint` and the recommendations of its strategy, replayed from the start.
+///
+/// ```
+/// use wordle::analysis::analyse;
+/// use wordle::game::Game;
+///
+/// let words: Vec<String> = ["CRANE", "CRATE", "GHOST", "SLATE"].iter().map(|word| word.to_string()).collect();
+/// let mut game = Game::new("CRANE".to_string(), false, 6, words);
+/// game.play("SLATE");
+/// game.play("CRANE");
+/// let analysis = analyse(&game);
+/// assert_eq!((analysis[0].before, analysis[0].after, analysis[0].bits), (4, 1, 2.0));
+/// assert_eq!((analysis[1].before, analysis[1].after, analysis[1].bits), (1, 1, 0.0));
+/// assert_eq!(analysis[1].best, "CRANE");
+/// ```
+pub fn analyse(game: &Game) -> Vec<GuessAnalysis> {
+    let mut replay: Game = Game::new(game.answer.clone(), game.is_difficult(), game.max_attempts, game.hint_list.clone());
+    replay.strategy = game.strategy;
+    replay.probe_list = game.probe_list.clone();
+    replay.strict = game.strict;
+
+    let mut analysis: Vec<GuessAnalysis> = Vec::new();
+    for (guess, _) in &game.guesses {
+        let candidates: Vec<String> = replay.get_hint();
+        let best: String = replay.get_recommend_words(&candidates).into_iter().next().unwrap_or_else(|| guess.clone());
+        replay.play(guess);
+        let after: usize = replay.get_hint().len();
+        analysis.push(GuessAnalysis {
+            guess: guess.clone(),
+            before: candidates.len(),
+            after,
+            // nothing to learn once the candidates ran out, with an answer missing from the hint list
+            bits: if candidates.is_empty() { 0.0 } else { (candidates.len() as f64 / after.max(1) as f64).log2() },
+            expected_bits: entropy(guess, &candidates),
+            expected: expected_left(guess, &candidates),
+            best_expected: expected_left(&best, &candidates),
+            best,
+        });
+    }
+    analysis
+}
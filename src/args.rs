@@ -1,342 +1,680 @@
-use core::panic;
 use std::{
-    fs::File,
-    io::{BufRead, BufReader},
-    path::PathBuf,
+    path::{Path, PathBuf},
     vec,
     fs,
 };
+use chrono::NaiveDate;
+use clap::{builder::RangedU64ValueParser, error::ErrorKind, Args, CommandFactory, Parser, Subcommand};
 use serde::Deserialize;
 
-use super::builtin_words;
+use wordle::daily::{default_epoch, Daily};
+use wordle::error::{ConfigError, WordListError};
+use wordle::export::Format;
+use wordle::game::{DEFAULT_MAX_ATTEMPTS, DEFAULT_WORD_LENGTH};
+use wordle::share::Palette;
+use wordle::solver::Strategy;
+use wordle::store::{Backend, Filter};
+use wordle::word_list;
+
+const DEFAULT_SEED: u64 = 101;
+const DEFAULT_DAY: usize = 1;
+
+/// Wordle in Rust
+#[derive(Parser)]
+#[command(name = "wordle", version, args_conflicts_with_subcommands = true)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    // `wordle [OPTIONS]` is short for `wordle play [OPTIONS]`
+    #[command(flatten)]
+    pub play: PlayArgs,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Play wordle (the default when no subcommand is given)
+    Play(PlayArgs),
+    /// Let the recommender guess a given answer
+    Solve(SolveArgs),
+    /// Suggest guesses for a game played elsewhere from the feedback typed in, like `crane gybbg`
+    Assist(AssistArgs),
+    /// Play every answer against every acceptable first guess and report the attempts
+    Bench(BenchArgs),
+    /// Print the statistics stored in a state file
+    Stats(StatsArgs),
+    /// Show a game stored in a state file again, turn by turn
+    Replay(ReplayArgs),
+    /// Grade every guess of stored games against the recommendations
+    #[command(alias = "analyze")]
+    Analyse(AnalyseArgs),
+    /// List the stored games that match every filter given
+    History(HistoryArgs),
+    /// Deduce the answers and the likely guesses behind a shared result block
+    Reverse(ReverseArgs),
+    /// Check a state file or upgrade it to the current schema
+    #[command(subcommand)]
+    State(StateCommand),
+}
+
+// options understood by every subcommand that needs word lists
+#[derive(Args, Clone, Default)]
+pub struct CommonArgs {
+    /// Load options from a JSON config file, command line options take precedence
+    #[arg(short, long, value_name = "FILE")]
+    pub config: Option<PathBuf>,
+
+    /// File with the answer candidates, one word per line
+    #[arg(short = 'f', long = "final-set", value_name = "FILE")]
+    pub final_set: Option<PathBuf>,
+
+    /// File with the acceptable guesses, one word per line
+    #[arg(short = 'a', long = "acceptable-set", value_name = "FILE")]
+    pub acceptable_set: Option<PathBuf>,
+
+    /// Number of letters in each word
+    #[arg(short = 'l', long = "length", value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+    pub length: Option<usize>,
+
+    /// Number of guesses allowed in each game
+    #[arg(short = 'm', long = "max-attempts", value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+    pub max_attempts: Option<usize>,
+
+    /// How recommendations are ranked: frequency, entropy or minimax
+    #[arg(long, value_name = "STRATEGY")]
+    pub strategy: Option<Strategy>,
+
+    /// Let the recommender suggest acceptable words that can no longer be the answer
+    #[arg(long)]
+    pub probes: bool,
+}
+
+// where and how result blocks are shared
+#[derive(Args, Clone, Default)]
+pub struct ShareArgs {
+    /// Append result blocks to FILE instead of showing them
+    #[arg(long, value_name = "FILE")]
+    pub share_file: Option<PathBuf>,
+
+    /// Share with orange and blue squares instead of green and yellow
+    #[arg(long)]
+    pub high_contrast: bool,
+}
+
+impl ShareArgs {
+    pub fn palette(&self) -> Palette {
+        if self.high_contrast { Palette::HighContrast } else { Palette::Standard }
+    }
+}
+
+// where the games of a subcommand that reads the history are kept
+#[derive(Args, Clone)]
+pub struct StoreArgs {
+    /// JSON file or SQLite database written by `--state`
+    #[arg(short = 'S', long, value_name = "FILE")]
+    pub state: PathBuf,
+
+    /// The games of the profile NAME, those played without a name by default
+    #[arg(short = 'P', long, value_name = "NAME")]
+    pub player: Option<String>,
+
+    /// Read a JSON file or an SQLite database, by the file extension unless given
+    #[arg(long, value_name = "STORE")]
+    pub store: Option<Backend>,
+}
+
+impl StoreArgs {
+    /// The profile as play names it, `None` for the games played without a name.
+    pub fn player(&self) -> Option<String> {
+        player_name(self.player.clone())
+    }
+}
+
+#[derive(Args, Clone, Default)]
+pub struct PlayArgs {
+    #[command(flatten)]
+    pub common: CommonArgs,
+
+    /// Use the given word as the answer
+    #[arg(short, long, conflicts_with_all = ["stats", "random"])]
+    pub word: Option<String>,
+
+    /// Pick answers pseudo-randomly from the final set
+    #[arg(short, long)]
+    pub random: bool,
+
+    /// Play the puzzle of the day, numbered like the original game and only once per date
+    #[arg(long, conflicts_with_all = ["word", "random", "day"])]
+    pub daily: bool,
+
+    /// Play the daily puzzle of DATE instead of today's, e.g. 2023-10-18
+    #[arg(long, value_name = "DATE", requires = "daily")]
+    pub date: Option<NaiveDate>,
+
+    /// Date of Wordle 0 in daily mode, 2021-06-19 by default
+    #[arg(long, value_name = "DATE")]
+    pub epoch: Option<NaiveDate>,
+
+    /// Guesses must make use of the hints revealed so far
+    #[arg(short = 'D', long)]
+    pub difficult: bool,
+
+    /// Every guess must fit the feedback of all guesses so far, with the broken hint explained
+    #[arg(long, alias = "ultra-hard")]
+    pub strict: bool,
+
+    /// Print statistics after each game
+    #[arg(short = 't', long)]
+    pub stats: bool,
+
+    /// Seed used to shuffle the final set in random and daily mode
+    #[arg(short, long)]
+    pub seed: Option<u64>,
+
+    /// Which answer of the shuffled final set to start from in random mode
+    #[arg(short, long, value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+    pub day: Option<usize>,
+
+    /// Load and save the game history in a JSON file
+    #[arg(short = 'S', long, value_name = "FILE")]
+    pub state: Option<PathBuf>,
+
+    /// Keep the history under the profile NAME of the state file, instead of asking for a name
+    #[arg(short = 'P', long, value_name = "NAME")]
+    pub player: Option<String>,
+
+    /// Keep the history in a JSON file or an SQLite database, by the file extension unless given
+    #[arg(long, value_name = "STORE")]
+    pub store: Option<Backend>,
+
+    /// Play in the terminal user interface
+    #[arg(short = 'T', long, conflicts_with = "gui")]
+    pub tui: bool,
+
+    /// Play in the graphical user interface
+    #[arg(short = 'G', long)]
+    pub gui: bool,
+
+    /// Show the remaining candidates and recommendations after each guess
+    #[arg(short = 'H', long)]
+    pub hint: bool,
+
+    /// Grade every guess against the recommendations after each game
+    #[arg(long, alias = "analyze")]
+    pub analyse: bool,
+
+    /// Share an emoji result block after each game
+    #[arg(long)]
+    pub share: bool,
+
+    #[command(flatten)]
+    pub share_args: ShareArgs,
+}
+
+#[derive(Args)]
+pub struct SolveArgs {
+    #[command(flatten)]
+    pub common: CommonArgs,
+
+    /// The answer the recommender has to find
+    pub word: String,
+
+    /// First guess, the best recommendation on the acceptable set by default
+    #[arg(long, value_name = "WORD")]
+    pub start: Option<String>,
+
+    /// Only make guesses allowed in difficult mode
+    #[arg(short = 'D', long)]
+    pub difficult: bool,
+}
+
+#[derive(Args)]
+pub struct AssistArgs {
+    #[command(flatten)]
+    pub common: CommonArgs,
+
+    /// The other game is in hard mode: guesses must make use of the hints revealed so far
+    #[arg(short = 'D', long)]
+    pub difficult: bool,
+
+    /// Every guess must fit the feedback of all guesses so far
+    #[arg(long, alias = "ultra-hard")]
+    pub strict: bool,
+
+    /// Assist in the terminal user interface
+    #[arg(short = 'T', long)]
+    pub tui: bool,
+}
+
+#[derive(Args)]
+pub struct BenchArgs {
+    #[command(flatten)]
+    pub common: CommonArgs,
+
+    /// Cache the feedback of every guess against every answer in FILE, built on the first run
+    #[arg(long, value_name = "FILE")]
+    pub matrix: Option<PathBuf>,
+
+    /// Play every game in difficult mode, recommending only guesses that may be typed
+    #[arg(short = 'D', long)]
+    pub difficult: bool,
+
+    /// Only print a summary line for each of these strategies, e.g. frequency,entropy,minimax
+    #[arg(long, value_name = "STRATEGIES", value_delimiter = ',', conflicts_with = "strategy")]
+    pub compare: Vec<Strategy>,
+}
+
+#[derive(Args)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
+pub struct StatsArgs {
+    #[command(subcommand)]
+    pub command: Option<StatsCommand>,
+
+    #[command(flatten)]
+    pub stored: Option<StoreArgs>, // always there without a subcommand
+
+    /// Share the result block of game GAME of the history instead, counting from 1, the last game by default
+    #[arg(long, value_name = "GAME", num_args = 0..=1, value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+    pub share: Option<Option<usize>>,
+
+    /// Compare every player of the state file instead
+    #[arg(long, conflicts_with_all = ["share", "player"])]
+    pub leaderboard: bool,
+
+    /// Also print the streaks, the guess distribution and the breakdowns when not on a terminal
+    #[arg(long, conflicts_with_all = ["share", "leaderboard"])]
+    pub detailed: bool,
+
+    #[command(flatten)]
+    pub share_args: ShareArgs,
+}
+
+#[derive(Subcommand)]
+pub enum StatsCommand {
+    /// Write every stored game, or every guess, for analysis elsewhere
+    Export(ExportArgs),
+    /// Combine the histories of state files, say from several machines, into one
+    Merge(MergeArgs),
+}
+
+#[derive(Args)]
+pub struct ExportArgs {
+    #[command(flatten)]
+    pub stored: StoreArgs,
+
+    /// csv or jsonl
+    #[arg(short, long, value_name = "FORMAT", default_value = "csv")]
+    pub format: Format,
 
-const WORD_LENGTH: usize = 5;
-#[derive(Deserialize)]
+    /// One row per guess with the feedback it got, instead of one per game
+    #[arg(long)]
+    pub guesses: bool,
+
+    /// Write to FILE instead of the standard output
+    #[arg(short, long, value_name = "FILE")]
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Args)]
+pub struct MergeArgs {
+    /// JSON files or SQLite databases written by `--state`
+    #[arg(value_name = "FILE", required = true)]
+    pub sources: Vec<PathBuf>,
+
+    /// Add the games to FILE, which is created if missing
+    #[arg(short, long, value_name = "FILE")]
+    pub output: PathBuf,
+}
+
+#[derive(Args)]
+pub struct ReplayArgs {
+    #[command(flatten)]
+    pub stored: StoreArgs,
+
+    /// Which game of the history, counting from 1, the last game by default
+    #[arg(value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+    pub game: Option<usize>,
+
+    /// Milliseconds between turns on a terminal
+    #[arg(long, value_name = "MS", default_value_t = 600)]
+    pub delay: u64,
+}
+
+#[derive(Args)]
+pub struct AnalyseArgs {
+    #[command(flatten)]
+    pub common: CommonArgs,
+
+    #[command(flatten)]
+    pub stored: StoreArgs,
+
+    /// Which game of the history, counting from 1, every game by default
+    #[arg(value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+    pub game: Option<usize>,
+}
+
+#[derive(Args)]
+pub struct ReverseArgs {
+    #[command(flatten)]
+    pub common: CommonArgs,
+
+    /// File with the result block, standard input by default
+    #[arg(value_name = "FILE")]
+    pub grid: Option<PathBuf>,
+
+    /// Only list the guesses that lead to WORD
+    #[arg(long, value_name = "WORD")]
+    pub answer: Option<String>,
+
+    /// Number of guess sequences to list, most likely first
+    #[arg(short = 'n', long, value_name = "COUNT", default_value_t = 5)]
+    pub sequences: usize,
+}
+
+#[derive(Args)]
+pub struct HistoryArgs {
+    #[command(flatten)]
+    pub stored: StoreArgs,
+
+    /// Only games won
+    #[arg(long, conflicts_with = "lost")]
+    pub won: bool,
+
+    /// Only games lost
+    #[arg(long)]
+    pub lost: bool,
+
+    /// Only games in difficult or strict mode
+    #[arg(long, conflicts_with = "normal")]
+    pub hard: bool,
+
+    /// Only games in neither difficult nor strict mode
+    #[arg(long)]
+    pub normal: bool,
+
+    /// Only games with the answer WORD
+    #[arg(long, value_name = "WORD")]
+    pub answer: Option<String>,
+}
+
+impl HistoryArgs {
+    pub fn filter(&self) -> Filter {
+        Filter {
+            won: (self.won || self.lost).then_some(self.won),
+            hard: (self.hard || self.normal).then_some(self.hard),
+            answer: self.answer.as_ref().map(|answer| answer.to_uppercase()),
+        }
+    }
+}
+
+#[derive(Subcommand)]
+pub enum StateCommand {
+    /// Upgrade a state file to the current schema, keeping the old one as its backup
+    Migrate(StateFileArgs),
+    /// Report what migrating would change and which games cannot have been played
+    Validate(StateFileArgs),
+}
+
+#[derive(Args)]
+pub struct StateFileArgs {
+    /// JSON file written by `--state`
+    #[arg(short = 'S', long, value_name = "FILE")]
+    pub state: PathBuf,
+}
+
+// resolved options: command line over config file over defaults
 pub struct WordProcessor {
     pub random_mode: bool, // random pattern
-    word_mode: bool, // specify words
-    pub meet_word_argument: bool, // if cmd determines word
+    pub daily: Option<Daily>, // the puzzle to play in daily mode
+    pub meet_word_argument: bool, // if the answer has been determined
     pub difficult_mode: bool, // difficult pattern
+    pub strict_mode: bool, // every guess must fit all the feedback
     pub stats_mode: bool, // output status after game ends
-    pub word_argument: Option<String>, 
-    seed_mode: bool, // if assigned seed
-    pub seed_argument: Option<u64>, 
-    day_mode: bool, // if assigned day
-    pub day_argument: Option<usize>, 
-    pub final_set_file: Option<String>, //final vocabulary file name
-    pub acceptable_set_file: Option<String>, // acceptable vocabulary file name
+    pub word_argument: Option<String>,
+    pub seed_argument: u64,
+    pub day_argument: usize,
+    pub final_set_file: Option<PathBuf>, //final vocabulary file name, None for builtin words
+    pub acceptable_set_file: Option<PathBuf>, // acceptable vocabulary file name, None for builtin words
     pub final_set: Vec<String>, // final vocabulary
     pub acceptable_set: Vec<String>, // acceptable vocabulary
     pub state_mode: bool, // save status
     pub state_path: Option<PathBuf>, // json path
-    pub config_mode: bool, // load argument
+    pub player: Option<String>, // profile in the state file, asked for on a terminal when missing
+    pub store: Option<Backend>, // how the state file is kept, by its extension when missing
     pub config_path: Option<PathBuf>, // json path
     pub tui_mode: bool, // start TUI
     pub gui_mode: bool, // start GUI
     pub hint_mode: bool, // need hint
-    pub test_mode: bool, // test attempts numbers
+    pub analysis_mode: bool, // grade the guesses after each game
+    pub share: Option<ShareArgs>, // share a result block after each game
+    pub word_length: usize, // letters in each word
+    pub max_attempts: usize, // guesses allowed in each game
+    pub strategy: Strategy, // how the recommender ranks guesses
+    pub probes: bool, // recommend words that can no longer be the answer
 }
 
-#[derive(Deserialize)]
+// every key is optional, missing keys fall back to the defaults
+#[derive(Deserialize, Default)]
 struct Config {
-    random: bool, 
-    difficult: bool,
-    stats: bool,
+    random: Option<bool>,
+    daily: Option<bool>,
+    epoch: Option<NaiveDate>,
+    difficult: Option<bool>,
+    strict: Option<bool>,
+    stats: Option<bool>,
+    analyse: Option<bool>,
     day: Option<usize>,
     seed: Option<u64>,
-    final_set: Option<String>,
-    acceptable_set: Option<String>,
+    final_set: Option<PathBuf>,
+    acceptable_set: Option<PathBuf>,
     state: Option<PathBuf>,
+    store: Option<Backend>,
+    player: Option<String>,
     word: Option<String>,
+    length: Option<usize>,
+    max_attempts: Option<usize>,
+    strategy: Option<Strategy>,
+    probes: Option<bool>,
 }
 
 impl WordProcessor {
     pub fn new() -> Self {
         WordProcessor {
             random_mode: false,
-            word_mode: false,
+            daily: None,
             meet_word_argument: false,
             difficult_mode: false,
+            strict_mode: false,
             stats_mode: false,
             word_argument: None,
-            seed_mode: false,
-            seed_argument: None,
-            day_mode: false,
-            day_argument: None,
-            final_set_file: Some("builtin_word.rs".to_string()), // default
-            acceptable_set_file: Some("builtin_word.rs".to_string()), //default
+            seed_argument: DEFAULT_SEED,
+            day_argument: DEFAULT_DAY,
+            final_set_file: None,
+            acceptable_set_file: None,
             final_set: vec![],
             acceptable_set: vec![],
             state_mode: false,
             state_path: None,
-            config_mode: false,
+            player: None,
+            store: None,
             config_path: None,
             tui_mode: false,
             gui_mode: false,
             hint_mode: false,
-            test_mode: false,
+            analysis_mode: false,
+            share: None,
+            word_length: DEFAULT_WORD_LENGTH,
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            strategy: Strategy::default(),
+            probes: false,
         }
     }
 
+    // the single precedence layer for word lists, length and attempts
+    pub fn from_common_args(args: &CommonArgs) -> anyhow::Result<Self> {
+        let config: Config = match &args.config {
+            Some(path) => Self::load_config_from_file(path)?,
+            None => Config::default(),
+        };
+        let mut processor: WordProcessor = Self::new();
+        processor.apply_common(args, &config)?;
+        Ok(processor)
+    }
 
-    pub fn process_args(&mut self, args: &[String]) {
-        self.final_set = get_default_answers_list();
-        self.acceptable_set = get_default_accept_list();
-        self.seed_argument = Some(101);
-        self.day_argument = Some(1);
-
-        if args.iter().any(|arg| arg == "-c" || arg == "--config") {
-            self.config_mode = true;
+    // the single precedence layer for playing
+    pub fn from_play_args(args: &PlayArgs) -> anyhow::Result<Self> {
+        let config: Config = match &args.common.config {
+            Some(path) => Self::load_config_from_file(path)?,
+            None => Config::default(),
+        };
+        let mut processor: WordProcessor = Self::new();
+        processor.apply_common(&args.common, &config)?;
+
+        processor.random_mode = args.random || config.random.unwrap_or(false);
+        processor.difficult_mode = args.difficult || config.difficult.unwrap_or(false);
+        processor.strict_mode = args.strict || config.strict.unwrap_or(false);
+        processor.stats_mode = args.stats || config.stats.unwrap_or(false);
+        processor.tui_mode = args.tui;
+        processor.gui_mode = args.gui;
+        processor.hint_mode = args.hint;
+        processor.analysis_mode = args.analyse || config.analyse.unwrap_or(false);
+        if args.share || args.share_args.share_file.is_some() {
+            processor.share = Some(args.share_args.clone());
         }
-
-        if let Some(index) = args.iter().position(|arg| arg == "-c" || arg == "--config") {
-            if index + 1 < args.len() {
-                let next_argument = &args[index + 1];
-                if let Ok(path) = next_argument.parse::<PathBuf>() {
-                    self.load_config_from_file(&Some(path.clone()));
-
-                    self.config_path = Some(path);
-                }
+        processor.state_path = args.state.clone().or(config.state);
+        processor.state_mode = processor.state_path.is_some();
+        processor.player = player_name(args.player.clone().or(config.player));
+        processor.store = args.store.or(config.store);
+
+        // a word from the command line beats random mode from the config file and vice versa
+        let word: Option<String> = if args.random { None } else { args.word.clone().or(config.word) };
+        if let Some(word) = word {
+            let word: String = word.trim().to_uppercase();
+            if word.len() != processor.word_length || !word.chars().all(|c| c.is_ascii_alphabetic()) {
+                return Err(Self::error(ErrorKind::InvalidValue, &format!("The answer should be a word of {} letters!", processor.word_length)).into());
             }
+            processor.random_mode = false;
+            processor.word_argument = Some(word);
+            processor.meet_word_argument = true;
         }
 
-        if args.iter().any(|arg| arg == "-w" || arg == "--word") {
-            self.random_mode = false;
-            self.word_mode = true;
-
-            if let Some(index) = args.iter().position(|arg| arg == "-w" || arg == "--word") {
-                if index + 1 < args.len() {
-                    let next_argument = &args[index + 1];
-                    if next_argument.chars().next().unwrap().is_alphabetic() {
-                        self.word_argument = Some(next_argument.to_string());
-                        self.meet_word_argument = true;
-                    }
-                }
+        // daily mode from the config file gives way to an answer or random mode from the command line
+        if args.daily || (config.daily.unwrap_or(false) && !args.random && args.word.is_none()) {
+            let epoch: NaiveDate = args.epoch.or(config.epoch).unwrap_or_else(default_epoch);
+            let daily: Option<Daily> = match args.date {
+                Some(date) => Daily::new(date, epoch),
+                None => Daily::today(epoch),
+            };
+            match daily {
+                Some(daily) => processor.daily = Some(daily),
+                None => return Err(Self::error(ErrorKind::ValueValidation, &format!("There is no daily puzzle before the epoch {}!", epoch)).into()),
             }
+            processor.random_mode = false;
+            processor.word_argument = None;
+            processor.meet_word_argument = false;
         }
 
-        if args.iter().any(|arg| arg == "-r" || arg == "--random") {
-            self.random_mode = true;
-        }
-
-        if args.iter().any(|arg| arg == "-D" || arg == "--difficult") {
-            self.difficult_mode = true;
+        let seed: Option<u64> = args.seed.or(config.seed);
+        let day: Option<usize> = args.day.or(config.day);
+        if !processor.random_mode && (day.is_some() || (seed.is_some() && processor.daily.is_none())) {
+            return Err(Self::error(ErrorKind::ArgumentConflict, "Please use -d/--day or -s/--seed options in random mode!").into());
         }
-
-        if args.iter().any(|arg| arg == "-t" || arg == "--stats") {
-            if self.meet_word_argument {
-                panic!("Tht -t/--stats option is not allowed when a word has been designated!");
-            } else {
-                self.stats_mode = true;
-            }
-        }
-
-        if let Some(index) = args.iter().position(|arg| arg == "-f" || arg == "--final-set") {
-            if index + 1 < args.len() {
-                let file_name = &args[index + 1];
-                if file_name.contains('.') {
-                    self.load_answer_list(file_name);
-
-                    self.final_set_file = Some(args[index + 1].to_string());
-                } else {
-                    println!("Ignoring invalid file name: {}, use default answer list!", file_name)
-                }
-            }
-        }    
-        
-        if let Some(index) = args.iter().position(|arg| arg == "-a" || arg == "--acceptable-set") {
-            if index + 1 < args.len() {
-                let file_name = &args[index + 1];
-                if file_name.contains('.') {
-                    self.load_accept_list(file_name);
-                    self.acceptable_set_file = Some(args[index + 1].to_string());
-                } else {
-                    println!("Ignoring invalid file name: {}, use default answer list!", file_name)
-                }
-            }
+        processor.seed_argument = seed.unwrap_or(DEFAULT_SEED);
+        processor.day_argument = day.unwrap_or(DEFAULT_DAY);
+        if !processor.validate_day() {
+            return Err(Self::error(ErrorKind::ValueValidation, "Invalid value for -d/--day option!").into());
         }
 
-        self.check_sets(&self.final_set, &self.acceptable_set); // final set must be strictly a subset of the acceptable list
+        Ok(processor)
+    }
 
-        if let Some(index) = args.iter().position(|arg| arg == "-s" || arg == "--seed") {
-            self.seed_mode = true;
-            if index + 1 < args.len() {
-                let next_argument = &args[index + 1];
-                if let Ok(seed) = next_argument.parse::<u64>() {
-                    self.seed_argument = Some(seed);
-                }
-            }
+    fn apply_common(&mut self, args: &CommonArgs, config: &Config) -> anyhow::Result<()> {
+        self.config_path = args.config.clone();
+        self.word_length = args.length.or(config.length).unwrap_or(DEFAULT_WORD_LENGTH);
+        self.max_attempts = args.max_attempts.or(config.max_attempts).unwrap_or(DEFAULT_MAX_ATTEMPTS);
+        if self.word_length == 0 {
+            return Err(Self::error(ErrorKind::ValueValidation, "Invalid value for -l/--length option!").into());
         }
-
-        if let Some(index) = args.iter().position(|arg| arg == "-d" || arg == "--day") {
-            self.day_mode = true;
-            if index + 1 < args.len() {
-                let next_argument = &args[index + 1];
-                if next_argument.contains("-") {
-                    panic!("Invalid value for -d/--day option!");
-                }
-                if let Ok(day) = next_argument.parse::<usize>() {
-                    self.day_argument = Some(day);
-                    if !self.validate_day() {
-                        panic!("Invalid value for -d/--day option!");
-                    }
-                }
-            }
+        if self.max_attempts == 0 {
+            return Err(Self::error(ErrorKind::ValueValidation, "Invalid value for -m/--max-attempts option!").into());
         }
 
-        if self.random_mode && self.word_mode {
-            panic!("The -w/--word option is not allowed in random mode!");
-        }
+        self.strategy = args.strategy.or(config.strategy).unwrap_or_default();
+        self.probes = args.probes || config.probes.unwrap_or(false);
 
-        if !self.random_mode && (self.day_mode|| self.seed_mode) {
-            panic!("Please use -d/--day or -s/--seed options in random mode!");
+        self.final_set_file = args.final_set.clone().or(config.final_set.clone());
+        self.acceptable_set_file = args.acceptable_set.clone().or(config.acceptable_set.clone());
+        match self.final_set_file.clone() {
+            Some(path) => self.load_answer_list(&path)?,
+            None => self.final_set = word_list::builtin_final(self.word_length),
         }
-        
-        if args.iter().any(|arg| arg == "-S" || arg == "--state") {
-            self.state_mode = true;
+        match self.acceptable_set_file.clone() {
+            Some(path) => self.load_accept_list(&path)?,
+            None => self.acceptable_set = word_list::builtin_acceptable(self.word_length),
         }
 
-        if let Some(index) = args.iter().position(|arg| arg == "-S" || arg == "--state") {
-            if index + 1 < args.len() {
-                let next_argument = &args[index + 1];
-                if let Ok(state) = next_argument.parse::<PathBuf>() {
-                    self.state_path = Some(state);
-                }
-            }
-        }
-        if args.iter().any(|arg| arg == "-T" || arg == "--tui") {
-            self.tui_mode = true;
-        }
-        
-        if args.iter().any(|arg| arg == "-G" || arg == "--gui") {
-            self.gui_mode = true;
-        }
-
-        if args.iter().any(|arg| arg == "-H" || arg == "--hint") {
-            self.hint_mode = true;
+        if self.final_set.is_empty() || self.acceptable_set.is_empty() {
+            return Err(Self::error(ErrorKind::InvalidValue, &format!("No words of length {}, please specify word lists with -f and -a!", self.word_length)).into());
         }
+        self.check_sets(&self.final_set, &self.acceptable_set)?;
+        Ok(())
+    }
 
-        if args.iter().any(|arg| arg == "--test") {
-            self.test_mode = true;
-        }
+    // errors in the style of clap's own, exiting with its usage status
+    fn error(kind: ErrorKind, message: &str) -> clap::Error {
+        Cli::command().error(kind, message)
     }
 
-    pub fn load_answer_list(&mut self, file_name: &str) {
-        if let Ok(file) = File::open(file_name) { // open file
-            self.final_set.clear(); 
-            let reader: BufReader<File> = BufReader::new(file); // read line by line
-            for line in reader.lines() {
-                if let Ok(word) = line {
-                    // check if there are multiple words in a line
-                    let words_in_a_line = word.split_whitespace().collect::<Vec<&str>>();
-                    if words_in_a_line.len() > 1 {
-                        panic!("There can be only one word in a line!");
-                    } else if word.len() != WORD_LENGTH {
-                        panic!("Each word should be 5 in length!");
-                    } else if self.final_set.contains(&word.trim().to_owned().to_uppercase()) {
-                        panic!("Duplicated!");
-                    }
-                    self.final_set.push(word.trim().to_owned().to_uppercase());
-                }
-            }
-        } else {
-            panic!("Failed to open file: {}", file_name);
-        }
+    pub fn load_answer_list(&mut self, file_name: &Path) -> Result<(), WordListError> {
+        self.final_set = word_list::load(file_name, self.word_length)?;
+        Ok(())
     }
 
-    pub fn load_accept_list(&mut self, file_name: &str) {
-        if let Ok(file) = File::open(file_name) {
-            self.acceptable_set.clear();
-            let reader: BufReader<File> = BufReader::new(file);
-            for line in reader.lines() {
-                if let Ok(word) = line {
-                    let words_in_a_line = word.split_whitespace().collect::<Vec<&str>>();
-                    // check multi words in a line
-                    if words_in_a_line.len() > 1 {
-                        panic!("There can be only one word in a line!");
-                    } else if word.len() != WORD_LENGTH{
-                        panic!("Each word should be 5 in length!");
-                    } else if self.acceptable_set.contains(&word.trim().to_owned().to_uppercase()) {
-                        panic!("Duplicated!");
-                    }
-                    self.acceptable_set.push(word.trim().to_owned().to_uppercase());
-                }
-            }
-            self.acceptable_set.sort();
-        } else {
-            panic!("Failed to open file: {}", file_name);
-        }
+    pub fn load_accept_list(&mut self, file_name: &Path) -> Result<(), WordListError> {
+        self.acceptable_set = word_list::load(file_name, self.word_length)?;
+        self.acceptable_set.sort();
+        Ok(())
     }
 
-    pub fn check_sets(&self, final_set: &Vec<String>, acceptable_set: &Vec<String>) {
-        for word in final_set {
-            if !acceptable_set.contains(word) {
-                panic!("acceptable_set does not include the word: {}!", word);
-            }
-        }
+    // final set must be a subset of the acceptable set
+    pub fn check_sets(&self, final_set: &[String], acceptable_set: &[String]) -> Result<(), WordListError> {
+        word_list::check_subset(final_set, &list_path(&self.final_set_file), acceptable_set, &list_path(&self.acceptable_set_file))
     }
 
     fn validate_day(&self) -> bool {
-        if self.day_argument.unwrap() == 0 {
+        if self.day_argument == 0 {
             return false;
-        } else if self.day_argument.unwrap() > self.final_set.len(){
+        } else if self.day_argument > self.final_set.len(){
             return false;
         }
         return true;
     }
 
-    fn load_config_from_file(&mut self, config_path: &Option<PathBuf>) {
-        if PathBuf::from(config_path.as_ref().unwrap()).exists() {
-            if let Ok(config) = serde_json::from_str::<Config>(
-                fs::read_to_string(config_path.as_ref().unwrap())
-                    .unwrap()
-                    .as_str()
-            ){
-                self.random_mode = config.random;
-                self.difficult_mode = config.difficult;
-                self.stats_mode = config.stats;
-                self.day_argument = config.day;
-                self.seed_argument = config.seed;
-                self.final_set_file = config.final_set;
-                self.acceptable_set_file = config.acceptable_set;
-                self.state_path = config.state;
-                self.word_argument = config.word;
-                match self.word_argument {
-                    Some(_) => {
-                        self.random_mode = false;
-                    },
-                    None => {
-                        self.random_mode = true;
-                    }
-                }
-            } else {
-                Self::new();
-            }
-        } else {
-            panic!("Config file doesn't exist!");
+    fn load_config_from_file(config_path: &Path) -> Result<Config, ConfigError> {
+        if !config_path.exists() {
+            return Err(ConfigError::MissingFile { path: config_path.to_path_buf() });
         }
-    } 
-    
-}    
-
-fn get_default_answers_list() -> Vec<String> {
-    let answer_list: Vec<String> = {
-        builtin_words::FINAL
-            .iter()
-            .map(|s| s.to_uppercase())
-            .collect()
-    };
-
-    answer_list
+        let content: String = fs::read_to_string(config_path)
+            .map_err(|source| ConfigError::Unreadable { path: config_path.to_path_buf(), source })?;
+        serde_json::from_str::<Config>(&content).map_err(|error| {
+            // serde_json appends the position to its message, keep it in the fields instead
+            let position: String = format!(" at line {} column {}", error.line(), error.column());
+            ConfigError::InvalidJson {
+                path: config_path.to_path_buf(),
+                line: error.line(),
+                column: error.column(),
+                message: error.to_string().trim_end_matches(&position).to_string(),
+            }
+        })
+    }
 }
 
-fn get_default_accept_list() -> Vec<String> {
-    let accept_list: Vec<String> = {
-        builtin_words::ACCEPTABLE
-            .iter()
-            .map(|s| s.to_uppercase())
-            .collect()
-    };
+// where a list came from, for error messages
+fn list_path(file: &Option<PathBuf>) -> PathBuf {
+    file.clone().unwrap_or_else(|| PathBuf::from(word_list::BUILTIN_PATH))
+}
 
-    accept_list
-}
\ No newline at end of file
+// surrounding spaces do not count, and a blank name is no name
+pub fn player_name(name: Option<String>) -> Option<String> {
+    name.map(|name| name.trim().to_string()).filter(|name| !name.is_empty())
+}
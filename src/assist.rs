@@ -0,0 +1,161 @@
+use std::fmt;
+
+use crate::constraints::Constraints;
+use crate::game::{validate_guess, ErrorType, GuessWordStatus, LetterStatus, ALPHABET_LENGTH, RECOMMEND_NUMBER};
+use crate::solver::{expected_left, Strategy};
+
+/// Why a line typed in assist mode was not taken.
+#[derive(Debug)]
+pub enum AssistError {
+    Malformed, // not a guess followed by its feedback
+    Guess(ErrorType), // the guess could not have been made
+    FeedbackLength(usize), // expected length
+    UnknownMark(char),
+    OutOfAttempts(usize), // the attempts every game has
+    NothingToUndo,
+}
+
+impl fmt::Display for AssistError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Malformed => write!(f, "Type a guess and its feedback, like \"crane gybbg\", or undo."),
+            Self::Guess(error) => write!(f, "{}", error),
+            Self::FeedbackLength(length) => write!(f, "The feedback should have {} marks, one per letter.", length),
+            Self::UnknownMark(mark) => write!(f, "'{}' is no feedback, use g for green, y for yellow and b for grey.", mark),
+            Self::OutOfAttempts(attempts) => write!(f, "A game is over after {} guesses, undo one first.", attempts),
+            Self::NothingToUndo => write!(f, "There is no guess to undo."),
+        }
+    }
+}
+
+impl std::error::Error for AssistError {}
+
+/// A word worth guessing next, with the candidates it is expected to leave.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Suggestion {
+    pub word: String,
+    pub expected: f64,
+}
+
+/// Parses feedback like `gybbg`: `g` for green, `y` for yellow and `b`, `x` or `-` for grey, or the squares of a result block.
+///
+/// ```
+/// use wordle::assist::parse_feedback;
+/// use wordle::game::LetterStatus::{Green, Red, Yellow};
+///
+/// assert_eq!(parse_feedback("GYbx-", 5).unwrap(), [Green, Yellow, Red, Red, Red]);
+/// assert_eq!(parse_feedback("🟩🟨⬛⬜🟩", 5).unwrap(), [Green, Yellow, Red, Red, Green]);
+/// assert!(parse_feedback("gyb", 5).is_err());
+/// ```
+pub fn parse_feedback(feedback: &str, word_length: usize) -> Result<GuessWordStatus, AssistError> {
+    let status: GuessWordStatus = feedback
+        .chars()
+        .map(|mark| match mark.to_ascii_lowercase() {
+            'g' | '🟩' | '🟧' => Ok(LetterStatus::Green),
+            'y' | '🟨' | '🟦' => Ok(LetterStatus::Yellow),
+            'b' | 'x' | '-' | '⬛' | '⬜' => Ok(LetterStatus::Red),
+            _ => Err(AssistError::UnknownMark(mark)),
+        })
+        .collect::<Result<_, _>>()?;
+    if status.len() != word_length {
+        return Err(AssistError::FeedbackLength(word_length));
+    }
+    Ok(status)
+}
+
+/// Keeps track of a game played elsewhere from the guesses made there and the feedback they got,
+/// suggesting what to guess next among the answers that still fit. The answer is never known.
+pub struct Assistant {
+    pub guesses: Vec<(String, GuessWordStatus)>, // as typed, with their feedback
+    pub strategy: Strategy, // how suggestions are ranked
+    pub probe_list: Vec<String>, // words worth guessing for information only, empty unless asked for
+    pub strict: bool, // every guess must fit the feedback of all guesses, not just the last one
+    difficult: bool,
+    max_attempts: usize,
+    constraints: Constraints, // what the feedback of every guess says about the answer
+    answers: Vec<String>, // words the answer may be
+    acceptable: Vec<String>, // words that may be guessed
+}
+
+impl Assistant {
+    /// Candidates are taken from `answers`, guesses from `acceptable`; set the strategy, probes and strict mode afterwards.
+    pub fn new(word_length: usize, difficult: bool, max_attempts: usize, answers: Vec<String>, acceptable: Vec<String>) -> Self {
+        Assistant {
+            guesses: Vec::new(),
+            strategy: Strategy::default(),
+            probe_list: Vec::new(),
+            strict: false,
+            difficult,
+            max_attempts,
+            constraints: Constraints::new(word_length),
+            answers,
+            acceptable,
+        }
+    }
+
+    /// Takes a typed guess and its feedback, like `crane gybbg`.
+    ///
+    /// ```
+    /// use wordle::assist::Assistant;
+    ///
+    /// let words: Vec<String> = ["CRANE", "CRATE", "GHOST", "SLATE"].iter().map(|word| word.to_string()).collect();
+    /// let mut assistant = Assistant::new(5, false, 6, words.clone(), words);
+    /// assistant.enter("slate bbggg").unwrap();
+    /// assert_eq!(assistant.candidates(), ["CRATE"]);
+    /// assert!(assistant.undo().is_ok());
+    /// assert_eq!(assistant.candidates().len(), 4);
+    /// ```
+    pub fn enter(&mut self, line: &str) -> Result<(), AssistError> {
+        let (guess, feedback) = match line.split_whitespace().collect::<Vec<&str>>()[..] {
+            [guess, feedback] => (guess, feedback),
+            _ => return Err(AssistError::Malformed),
+        };
+        if self.get_tries() >= self.max_attempts {
+            return Err(AssistError::OutOfAttempts(self.max_attempts));
+        }
+        let guess: String = validate_guess(guess, &self.acceptable, &self.constraints, self.difficult, self.strict).map_err(AssistError::Guess)?;
+        let status: GuessWordStatus = parse_feedback(feedback, self.constraints.word_length())?;
+        self.constraints.add(&guess, &status);
+        self.guesses.push((guess, status));
+        Ok(())
+    }
+
+    /// Forgets the last guess, as if it had never been typed.
+    pub fn undo(&mut self) -> Result<(), AssistError> {
+        self.guesses.pop().ok_or(AssistError::NothingToUndo)?;
+        self.constraints = Constraints::new(self.constraints.word_length());
+        for (guess, status) in &self.guesses {
+            self.constraints.add(guess, status);
+        }
+        Ok(())
+    }
+
+    /// Guesses typed so far.
+    pub fn get_tries(&self) -> usize {
+        self.guesses.len()
+    }
+
+    /// The best status of every letter so far, for colouring a keyboard.
+    pub fn alphabet(&self) -> [LetterStatus; ALPHABET_LENGTH] {
+        self.constraints.alphabet()
+    }
+
+    /// Whether the last feedback was all green.
+    pub fn is_solved(&self) -> bool {
+        self.guesses.last().is_some_and(|(_, status)| status.iter().all(|&letter| letter == LetterStatus::Green))
+    }
+
+    /// The answers still consistent with every feedback.
+    pub fn candidates(&self) -> Vec<String> {
+        self.answers.iter().filter(|word| self.constraints.allows(word)).cloned().collect()
+    }
+
+    /// Up to five words worth guessing next among `candidates` and the probes that may be guessed next, best first.
+    pub fn suggestions(&self, candidates: &[String]) -> Vec<Suggestion> {
+        let legal = |words: &[String]| -> Vec<String> {
+            words.iter().filter(|word| self.constraints.may_guess(word, self.difficult, self.strict)).cloned().collect()
+        };
+        let words: Vec<String> = self.strategy.recommend(&legal(candidates), &legal(&self.probe_list), RECOMMEND_NUMBER);
+        words.into_iter().map(|word| Suggestion { expected: expected_left(&word, candidates), word }).collect()
+    }
+}
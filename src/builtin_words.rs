@@ -1,3 +1,4 @@
+/// Possible answers, lowercase and sorted.
 pub const FINAL: &[&str] = &[
     "aback", "abase", "abate", "abbey", "abbot", "abhor", "abide", "abled", "abode", "abort",
     "about", "above", "abuse", "abyss", "acorn", "acrid", "actor", "acute", "adage", "adapt",
@@ -232,6 +233,7 @@ pub const FINAL: &[&str] = &[
     "wrist", "write", "wrong", "wrote", "wrung", "wryly", "yacht", "yearn", "yeast", "yield",
     "young", "youth", "zebra", "zesty", "zonal",
 ];
+/// Words accepted as guesses, lowercase and sorted; a superset of `FINAL`.
 pub const ACCEPTABLE: &[&str] = &[
     "aahed", "aalii", "aargh", "aarti", "abaca", "abaci", "aback", "abacs", "abaft", "abaka",
     "abamp", "aband", "abase", "abash", "abask", "abate", "abaya", "abbas", "abbed", "abbes",
@@ -0,0 +1,568 @@
+use std::{
+    io::{self, Write},
+    thread,
+    time::Duration,
+};
+use colored::Colorize;
+
+use wordle::analysis::GuessAnalysis;
+use wordle::assist::{AssistError, Assistant, Suggestion};
+use wordle::frontend::{AssistFrontend, InputSource, Renderer};
+use wordle::game::{ErrorType, Game, GuessWordStatus, LetterStatus};
+use wordle::reverse::{Deduction, Sequence};
+use wordle::stats::{Distribution, Record, Standing, Stats};
+
+use crate::args::player_name;
+
+// plain command line front end: colours for a terminal, letters for the test protocol
+
+pub fn error_message(error: &ErrorType, is_tty: bool) -> String {
+    if is_tty {
+        match error {
+            ErrorType::WrongLength(length) => format!("{}{}{}", "The length of a word should be ".red(), length.to_string().green(), ", please try another word!".red()),
+            ErrorType::HintUnused | ErrorType::Inconsistent(_) => error.to_string(),
+            _ => error.to_string().red().to_string(),
+        }
+    } else {
+        String::from("INVALID")
+    }
+}
+
+pub fn print_colored_alphabet(game: &Game) {
+    let result: String = game
+        .alphabet()
+        .iter()
+        .enumerate()
+        .map(|(i, status)| {
+            let c: char = (i as u8 + b'A') as char;
+            match status {
+                LetterStatus::Green => c.to_string().green().to_string(),
+                LetterStatus::Red => c.to_string().red().to_string(),
+                LetterStatus::Yellow => c.to_string().yellow().to_string(),
+                _ => c.to_string(),
+            }
+        })
+        .collect();
+
+    println!("{}", result);
+}
+
+pub fn print_status_alphabet(game: &Game) {
+    let result: String = game
+        .alphabet()
+        .iter()
+        .map(|status: &LetterStatus| match status {
+            LetterStatus::Green => "G".to_string(),
+            LetterStatus::Red => "R".to_string(),
+            LetterStatus::Yellow => "Y".to_string(),
+            _ => "X".to_string(),
+        })
+        .collect();
+
+    println!("{}", result);
+}
+
+pub fn print_colored_word(word: &str, result: &GuessWordStatus) {
+    let colored_word: String = word
+        .chars()
+        .enumerate()
+        .map(|(i, c)| match result[i] {
+            LetterStatus::Green => c.to_string().green().to_string(),
+            LetterStatus::Red => c.to_string().red().to_string(),
+            LetterStatus::Yellow => c.to_string().yellow().to_string(),
+            _ => c.to_string(),
+        })
+        .collect();
+
+    print!("{} ", colored_word);
+}
+
+pub fn print_status_word(word: &str, result: &GuessWordStatus) {
+    let colored_word: String = word
+        .chars()
+        .enumerate()
+        .map(|(i, _c)| match result[i] {
+            LetterStatus::Green => "G",
+            LetterStatus::Red =>"R",
+            LetterStatus::Yellow => "Y",
+            _ => "X",
+        })
+        .collect();
+
+    print!("{} ", colored_word);
+}
+
+pub fn print_guess_history(game: &Game) {
+    for (word, result) in &game.guesses {
+        print_colored_word(word, result);
+        print_colored_alphabet(game);
+    }
+    println!();
+}
+
+// to be called once `is_game_over` returned true
+pub fn print_game_over(game: &Game, is_tty: bool) {
+    let tries_number: usize = game.get_tries();
+    if game.is_win {
+        if is_tty {
+            println!("You used {} chances and get the answer!", tries_number.to_string().green());
+        } else {
+            println!("CORRECT {}", tries_number);
+        }
+    } else if is_tty {
+        println!("{} The answer is: {}", "FAILED!".red(), game.answer.green());
+    } else {
+        println!("FAILED {}", game.answer);
+    }
+}
+
+pub fn print_stats(stats: &Stats, is_tty: bool) {
+    let words: Vec<(&String, &usize)> = stats.get_frequent_words();
+
+    if is_tty {
+        println!("You winned {} games, lost {} games!", stats.get_wins().to_string().blue(), stats.get_fails().to_string().blue());
+        println!("Your chance of winning is {:.2}", stats.get_success_rate().to_string().blue());
+        println!("The words that you use most frequently are:");
+
+        for (word, count) in words.iter().take(5) {
+            print!("{} {} ", word.to_string().green(), count.to_string().blue());
+        }
+        println!();
+        for line in stats_panel(stats) {
+            println!("{}", line);
+        }
+        let standings: Vec<Standing> = stats.get_leaderboard();
+        if !standings.is_empty() {
+            println!("Leaderboard:");
+            print_leaderboard(&standings, is_tty);
+        }
+    } else {
+        print!("{} {} {:.2}", stats.get_wins(), stats.get_fails(), stats.get_average_tries());
+        println!();
+        for (i, &(word, count)) in words.iter().enumerate().take(5) {
+            print!("{} {}", word, count);
+            if i < 4 && i < words.len() - 1 {
+                print!(" ");
+            }
+        }
+    }
+
+    println!();
+}
+
+const BAR_WIDTH: usize = 30;
+
+// the statistics panel: streaks, guess distribution as ASCII bars, then results per mode and per word list
+pub fn stats_panel(stats: &Stats) -> Vec<String> {
+    let distribution: Distribution = stats.get_distribution();
+    let most: usize = distribution.wins.iter().copied().fold(distribution.fails, usize::max).max(1);
+    let bar = |label: String, count: usize| format!("{} |{} {}", label, "#".repeat((count * BAR_WIDTH).div_ceil(most)), count);
+
+    let mut lines: Vec<String> = vec![
+        format!("Current streak {}, max streak {}", stats.get_current_streak(), stats.get_max_streak()),
+        "Guess distribution:".to_string(),
+    ];
+    lines.extend(distribution.wins.iter().enumerate().map(|(i, &count)| bar((i + 1).to_string(), count)));
+    lines.push(bar("X".to_string(), distribution.fails));
+    lines.push(format!("Average guesses {:.2}", stats.get_average_tries()));
+    let breakdowns = stats
+        .get_mode_breakdown()
+        .into_iter()
+        .map(|(mode, summary)| (mode.to_string(), summary))
+        .chain(stats.get_word_list_breakdown());
+    for (name, summary) in breakdowns {
+        lines.push(format!("{}: {} played, {:.0}% won, {:.2} guesses", name, summary.games(), summary.success_rate() * 100.0, summary.average_tries()));
+    }
+    lines
+}
+
+// players best first, one per line
+pub fn leaderboard_table(standings: &[Standing]) -> Vec<String> {
+    let width: usize = standings.iter().map(|standing| standing.player.chars().count()).fold("Player".len(), usize::max);
+    let mut lines: Vec<String> = vec![format!("{:<width$}  {:>4}  {:>6}  {:>7}  {:>6}  {:>3}", "Player", "Won", "Played", "Guesses", "Streak", "Max")];
+    for standing in standings {
+        lines.push(format!(
+            "{:<width$}  {:>3.0}%  {:>6}  {:>7.2}  {:>6}  {:>3}",
+            standing.player,
+            standing.summary.success_rate() * 100.0,
+            standing.summary.games(),
+            standing.summary.average_tries(),
+            standing.current_streak,
+            standing.max_streak
+        ));
+    }
+    lines
+}
+
+// a table on a terminal; otherwise name, wins, fails, average guesses, current and max streak
+pub fn print_leaderboard(standings: &[Standing], is_tty: bool) {
+    if is_tty {
+        for line in leaderboard_table(standings) {
+            println!("{}", line);
+        }
+    } else {
+        for standing in standings {
+            println!(
+                "{} {} {} {:.2} {} {}",
+                standing.player,
+                standing.summary.wins,
+                standing.summary.fails,
+                standing.summary.average_tries(),
+                standing.current_streak,
+                standing.max_streak
+            );
+        }
+    }
+}
+
+// one line per guess: candidates before and after, bits gained, candidates expected to be left by the guess and by the recommendation
+pub fn analysis_table(analysis: &[GuessAnalysis]) -> Vec<String> {
+    let width: usize = analysis.iter().map(|guess| guess.guess.len().max(guess.best.len())).fold("Guess".len(), usize::max);
+    let mut lines: Vec<String> =
+        vec![format!("{:<width$}  {:>6}  {:>5}  {:>5}  {:>6}  {:<width$}  {:>6}  {:>5}  {:>5}", "Guess", "Before", "After", "Bits", "Expect", "Best", "Expect", "Skill", "Luck")];
+    for guess in analysis {
+        lines.push(format!(
+            "{:<width$}  {:>6}  {:>5}  {:>5.2}  {:>6.1}  {:<width$}  {:>6.1}  {:>4.0}%  {:>+5.2}",
+            guess.guess,
+            guess.before,
+            guess.after,
+            guess.bits,
+            guess.expected,
+            guess.best,
+            guess.best_expected,
+            guess.skill() * 100.0,
+            guess.luck()
+        ));
+    }
+    lines
+}
+
+// a table on a terminal; otherwise guess, candidates before and after, bits, expected left, best guess, its expected left, skill and luck
+pub fn print_analysis(analysis: &[GuessAnalysis], is_tty: bool) {
+    if is_tty {
+        for line in analysis_table(analysis) {
+            println!("{}", line);
+        }
+    } else {
+        for guess in analysis {
+            println!(
+                "{} {} {} {:.2} {:.2} {} {:.2} {:.2} {:.2}",
+                guess.guess,
+                guess.before,
+                guess.after,
+                guess.bits,
+                guess.expected,
+                guess.best,
+                guess.best_expected,
+                guess.skill(),
+                guess.luck()
+            );
+        }
+    }
+}
+
+// averages over all `analysis`: skill as a share of the recommendations, luck in bits
+pub fn print_analysis_summary(analysis: &[GuessAnalysis], is_tty: bool) {
+    let count: f64 = analysis.len().max(1) as f64;
+    let skill: f64 = analysis.iter().map(GuessAnalysis::skill).sum::<f64>() / count;
+    let luck: f64 = analysis.iter().map(GuessAnalysis::luck).sum::<f64>() / count;
+    if is_tty {
+        println!("{} guesses, {:.0}% skill and {:+.2} bits of luck on average", analysis.len(), skill * 100.0, luck);
+    } else {
+        println!("{} {:.2} {:.2}", analysis.len(), skill, luck);
+    }
+}
+
+pub fn print_history(games: &[(usize, Record)], is_tty: bool) {
+    if is_tty {
+        if games.is_empty() {
+            println!("No games match");
+        }
+        for (number, record) in games {
+            let result: String = if record.is_win() { format!("{}/{}", record.guesses.len(), record.max_attempts()) } else { format!("X/{}", record.max_attempts()) };
+            let mode: &str = if record.strict { " strict" } else if record.difficult { " difficult" } else { "" };
+            let date: String = record.date.map_or(String::new(), |date| format!(" {}", date));
+            println!("{} {} {}{}{}", console::style(format!("Game {:>3}", number)).bold(), record.answer, result, mode, date);
+        }
+    } else {
+        for (number, record) in games {
+            let tries: String = if record.is_win() { record.guesses.len().to_string() } else { "X".to_string() };
+            println!("{} {} {} {}", number, record.answer, tries, record.guesses.join(" "));
+        }
+    }
+}
+
+// the answers that fit a result block, most likely first; only the first ones on a terminal
+pub fn print_deductions(deductions: &[Deduction], is_tty: bool) {
+    if !is_tty {
+        println!("{}", deductions.len());
+        for deduction in deductions {
+            println!("{} {:.4}", deduction.answer, deduction.likelihood);
+        }
+        return;
+    }
+    match deductions.len() {
+        0 => println!("{}", "No answer fits the result block.".red()),
+        1 => println!("Only {} fits the result block.", deductions[0].answer.green()),
+        count => println!("{} answers fit the result block, the likeliest:", count.to_string().blue()),
+    }
+    if deductions.len() > 1 {
+        for deduction in deductions.iter().take(CANDIDATES_SHOWN) {
+            println!("{} {:5.1}%", deduction.answer, deduction.likelihood * 100.0);
+        }
+        if deductions.len() > CANDIDATES_SHOWN {
+            println!("and {} more", deductions.len() - CANDIDATES_SHOWN);
+        }
+    }
+}
+
+// guesses that give `rows`, coloured by them on a terminal, each with the odds of playing it
+pub fn print_sequences(sequences: &[Sequence], rows: &[GuessWordStatus], is_tty: bool) {
+    if is_tty {
+        match sequences.len() {
+            0 => println!("No guesses give these rows if every guess could still have been the answer."),
+            _ => println!("Likely guesses, by a player who only guesses words that may still be the answer:"),
+        }
+    }
+    for sequence in sequences {
+        if is_tty {
+            print!("1 in {:<8} ", sequence.odds);
+            for (guess, row) in sequence.guesses.iter().zip(rows) {
+                print_colored_word(guess, row);
+            }
+            println!();
+        } else {
+            println!("{} {}", sequence.guesses.join(" "), sequence.odds);
+        }
+    }
+}
+
+// game `number` of a state file turn by turn, `delay` apart on a terminal, in the same letters as when it was played otherwise
+pub fn print_replay(record: &Record, number: usize, is_tty: bool, delay: Duration) {
+    let mut game: Game = Game::new(record.answer.clone(), record.difficult, record.max_attempts(), Vec::new());
+    if is_tty {
+        let mut details: Vec<String> = Vec::new();
+        if let (Some(started_at), Some(duration)) = (record.started_at, record.duration_secs) {
+            details.push(format!("played {} in {}s", started_at.format("%Y-%m-%d %H:%M"), duration));
+        }
+        if let Some(puzzle) = record.puzzle {
+            details.push(format!("Wordle {}", puzzle));
+        }
+        if record.random {
+            details.push(format!("random day {} seed {}", record.day.unwrap_or_default(), record.seed.unwrap_or_default()));
+        }
+        if record.strict {
+            details.push("strict".to_string());
+        } else if record.difficult {
+            details.push("difficult".to_string());
+        }
+        if record.hints_shown > 0 {
+            details.push(format!("{} hints, {} recommendations", record.hints_shown, record.recommendations_shown));
+        }
+        println!("{} {}", console::style(format!("Game {}", number)).bold(), details.join(", "));
+    }
+
+    for word in &record.guesses {
+        let status: GuessWordStatus = game.play(word);
+        if is_tty {
+            thread::sleep(delay);
+            print_colored_word(word, &status);
+            print_colored_alphabet(&game);
+        } else {
+            print_status_word(word, &status);
+            print_status_alphabet(&game);
+        }
+    }
+    if let Some(word) = record.guesses.last() {
+        game.is_game_over(word);
+    }
+    print_game_over(&game, is_tty);
+}
+
+/// Reads lines from stdin; coloured output on a terminal, the letter protocol otherwise.
+pub struct CliFrontend {
+    is_tty: bool,
+    show_stats: bool, // print statistics after every game
+}
+
+impl CliFrontend {
+    pub fn new(is_tty: bool, show_stats: bool) -> Self {
+        CliFrontend { is_tty, show_stats }
+    }
+
+    // on a terminal, ask for the name of the player unless it is known already
+    pub fn greet(&mut self, player: &mut Option<String>) {
+        if self.is_tty {
+            if player.is_none() {
+                print!("{}", console::style("Your name: ").bold().red());
+                io::stdout().flush().unwrap();
+                *player = player_name(read_line());
+            }
+            println!("Welcome to wordle, {}!", player.as_deref().unwrap_or_default());
+        }
+    }
+}
+
+// `None` at the end of the input
+fn read_line() -> Option<String> {
+    let mut line: String = String::new();
+    match io::stdin().read_line(&mut line) {
+        Ok(0) | Err(_) => None,
+        Ok(_) => Some(line),
+    }
+}
+
+impl InputSource for CliFrontend {
+    fn read_answer(&mut self) -> Option<String> {
+        if self.is_tty {
+            println!("Please type in the answer to start the game: ");
+        }
+        read_line()
+    }
+
+    fn read_guess(&mut self, game: &Game) -> Option<String> {
+        if self.is_tty {
+            println!("ROUND{}:", game.get_tries() + 1);
+            println!("Enter your guess: ");
+        }
+        read_line()
+    }
+
+    fn ask_continue(&mut self) -> bool {
+        loop {
+            if self.is_tty {
+                print!(
+                    "Would you like to start a new game? {} ",
+                    console::style("[Y/N]").bold().blue()
+                );
+            }
+            io::stdout().flush().unwrap();
+            let input: String = match read_line() {
+                Some(input) => input,
+                None => return false,
+            };
+
+            match input.trim().to_lowercase().as_str() {
+                "y" => return true,
+                "n" => return false,
+                _ => continue,
+            }
+        }
+    }
+
+    fn ask_recommend(&mut self) -> bool {
+        println!("Do you need recommendations for the most likely words?");
+        println!("<y> for yes, <n> for No");
+        read_line().is_some_and(|request| request.trim().eq_ignore_ascii_case("y"))
+    }
+}
+
+impl Renderer for CliFrontend {
+    fn render_start(&mut self, game: &Game) {
+        if let Some(daily) = &game.daily {
+            if self.is_tty {
+                println!("{} {}", console::style(daily).bold(), daily.date);
+            } else {
+                println!("{}", daily);
+            }
+        }
+        if self.is_tty {
+            println!("You have {} chances to guess the word!", game.max_attempts);
+            println!();
+        }
+    }
+
+    fn render_invalid(&mut self, error: &ErrorType) {
+        println!("{}", error_message(error, self.is_tty));
+    }
+
+    fn render_guess(&mut self, game: &Game) {
+        if self.is_tty {
+            print_guess_history(game);
+        } else {
+            let (word, status) = &game.guesses[game.get_tries() - 1];
+            print_status_word(word, status);
+            print_status_alphabet(game);
+        }
+    }
+
+    fn render_candidates(&mut self, candidates: &[String]) {
+        println!("Here are all possible words");
+        for candidate in candidates {
+            println!("{}", candidate);
+        }
+        println!();
+    }
+
+    fn render_recommendations(&mut self, words: &[String]) {
+        for (index, word) in words.iter().enumerate() {
+            println!("{}: {}", index + 1, word);
+        }
+    }
+
+    fn render_game_over(&mut self, game: &Game) {
+        print_game_over(game, self.is_tty);
+    }
+
+    fn render_analysis(&mut self, analysis: &[GuessAnalysis]) {
+        print_analysis(analysis, self.is_tty);
+    }
+
+    fn render_share(&mut self, text: &str) {
+        println!("{}", text);
+    }
+
+    fn render_stats(&mut self, stats: &Stats) {
+        if self.show_stats {
+            print_stats(stats, self.is_tty);
+        }
+    }
+}
+
+// candidates listed on a terminal before the rest are only counted
+const CANDIDATES_SHOWN: usize = 20;
+
+impl AssistFrontend for CliFrontend {
+    fn read_entry(&mut self, assistant: &Assistant) -> Option<String> {
+        if self.is_tty {
+            println!("Guess {} and its feedback, g for green, y for yellow and b for grey (or undo):", assistant.get_tries() + 1);
+        }
+        read_line()
+    }
+
+    fn render_entry_error(&mut self, error: &AssistError) {
+        if self.is_tty {
+            println!("{}", error.to_string().red());
+        } else {
+            println!("INVALID");
+        }
+    }
+
+    // on a terminal the board, candidates and suggestions; otherwise the count of candidates, then each suggestion and the candidates it is expected to leave
+    fn render_assistant(&mut self, assistant: &Assistant, candidates: &[String], suggestions: &[Suggestion]) {
+        if !self.is_tty {
+            let suggested = suggestions.iter().map(|suggestion| format!(" {} {:.2}", suggestion.word, suggestion.expected));
+            println!("{}{}", candidates.len(), suggested.collect::<String>());
+            return;
+        }
+        for (word, status) in &assistant.guesses {
+            print_colored_word(word, status);
+            println!();
+        }
+        if assistant.is_solved() {
+            println!("Solved in {} guesses!", assistant.get_tries());
+            return;
+        }
+        match candidates.len() {
+            0 => println!("{}", "No word fits all the feedback, undo the last guess to check it.".red()),
+            count => {
+                let shown: Vec<&str> = candidates.iter().take(CANDIDATES_SHOWN).map(String::as_str).collect();
+                let more: String = if count > CANDIDATES_SHOWN { format!(" and {} more", count - CANDIDATES_SHOWN) } else { String::new() };
+                println!("{} candidates: {}{}", count.to_string().blue(), shown.join(" "), more);
+            }
+        }
+        for (i, suggestion) in suggestions.iter().enumerate() {
+            println!("{}: {} leaves {:.1} on average", i + 1, suggestion.word.green(), suggestion.expected);
+        }
+    }
+}
@@ -0,0 +1,204 @@
+use std::fmt;
+
+use crate::game::{LetterStatus, ALPHABET_LENGTH};
+
+/// Which part of the feedback so far a guess ignores; positions count from 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Violation {
+    MustBe { position: usize, letter: char }, // a green letter was moved
+    CannotBe { position: usize, letter: char }, // the letter was yellow or red there
+    TooFew { letter: char, min: usize },
+    TooMany { letter: char, max: usize },
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MustBe { position, letter } => write!(f, "Letter {} must be {}.", position, letter),
+            Self::CannotBe { position, letter } => write!(f, "Letter {} cannot be {}.", position, letter),
+            Self::TooFew { letter, min } => write!(f, "The word must contain at least {} {}.", min, letter),
+            Self::TooMany { letter, max: 0 } => write!(f, "The word must not contain {}.", letter),
+            Self::TooMany { letter, max } => write!(f, "The word may contain at most {} {}.", max, letter),
+        }
+    }
+}
+
+/// Everything the feedback of all guesses so far says about the answer.
+/// Hints, both hard modes, the keyboard colours and the solver all read it; positions count from 0.
+///
+/// ```
+/// use wordle::constraints::Constraints;
+/// use wordle::game::{get_status, LetterStatus};
+///
+/// let mut constraints = Constraints::new(5);
+/// constraints.add("TRACE", &get_status("TRACE", "CRANE"));
+/// assert_eq!(constraints.known_at(1), Some('R'));
+/// assert!(!constraints.allowed_at(3).contains(&'C'));
+/// assert_eq!((constraints.min_count('C'), constraints.max_count('T')), (1, 0));
+/// assert_eq!(constraints.letter_status('C'), LetterStatus::Yellow);
+/// assert!(constraints.allows("CRANE") && constraints.uses_hints("CRACE") && !constraints.allows("CRACE"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Constraints {
+    greens: Vec<Option<u8>>, // letters found in place
+    allowed: Vec<[bool; ALPHABET_LENGTH]>, // letters still possible at each position
+    min_count: [usize; ALPHABET_LENGTH],
+    max_count: [usize; ALPHABET_LENGTH],
+}
+
+fn letter_index(c: u8) -> usize {
+    (c - b'A') as usize
+}
+
+fn letter(index: usize) -> char {
+    (index as u8 + b'A') as char
+}
+
+impl Constraints {
+    /// No feedback yet, anything of `word_length` letters goes.
+    pub fn new(word_length: usize) -> Self {
+        Constraints {
+            greens: vec![None; word_length],
+            allowed: vec![[true; ALPHABET_LENGTH]; word_length],
+            min_count: [0; ALPHABET_LENGTH],
+            max_count: [word_length; ALPHABET_LENGTH],
+        }
+    }
+
+    /// Letters in every word the feedback is about.
+    pub fn word_length(&self) -> usize {
+        self.greens.len()
+    }
+
+    /// Narrows the constraints with the feedback `status` for `word`.
+    pub fn add(&mut self, word: &str, status: &[LetterStatus]) {
+        let mut found: [usize; ALPHABET_LENGTH] = [0; ALPHABET_LENGTH]; // green and yellow copies
+        let mut capped: [bool; ALPHABET_LENGTH] = [false; ALPHABET_LENGTH]; // a red copy means there are no more
+
+        for (position, (c, letter_status)) in word.bytes().zip(status).enumerate() {
+            let index: usize = letter_index(c);
+            match letter_status {
+                LetterStatus::Green => {
+                    self.greens[position] = Some(c);
+                    self.allowed[position] = [false; ALPHABET_LENGTH];
+                    self.allowed[position][index] = true;
+                    found[index] += 1;
+                }
+                LetterStatus::Yellow => {
+                    self.allowed[position][index] = false;
+                    found[index] += 1;
+                }
+                LetterStatus::Red => {
+                    self.allowed[position][index] = false;
+                    capped[index] = true;
+                }
+                LetterStatus::Unknown => {}
+            }
+        }
+
+        for index in 0..ALPHABET_LENGTH {
+            self.min_count[index] = self.min_count[index].max(found[index]);
+            if capped[index] {
+                self.max_count[index] = self.max_count[index].min(found[index]);
+            }
+        }
+    }
+
+    /// The letter found at `position`, if any.
+    pub fn known_at(&self, position: usize) -> Option<char> {
+        self.greens[position].map(|c| c as char)
+    }
+
+    /// Letters the answer may still have at `position`, in alphabetical order.
+    pub fn allowed_at(&self, position: usize) -> Vec<char> {
+        (0..ALPHABET_LENGTH).filter(|&index| self.allowed[position][index]).map(letter).collect()
+    }
+
+    /// Fewest copies of the uppercase `letter` the answer has.
+    pub fn min_count(&self, letter: char) -> usize {
+        self.min_count[letter_index(letter as u8)]
+    }
+
+    /// Most copies of the uppercase `letter` the answer may have.
+    pub fn max_count(&self, letter: char) -> usize {
+        self.max_count[letter_index(letter as u8)]
+    }
+
+    /// The best news about `letter` so far, as the keyboard shows it.
+    pub fn letter_status(&self, letter: char) -> LetterStatus {
+        let index: usize = letter_index(letter as u8);
+        if self.greens.contains(&Some(letter as u8)) {
+            LetterStatus::Green
+        } else if self.min_count[index] > 0 {
+            LetterStatus::Yellow
+        } else if self.max_count[index] == 0 {
+            LetterStatus::Red
+        } else {
+            LetterStatus::Unknown
+        }
+    }
+
+    /// `letter_status` of every letter from A to Z.
+    pub fn alphabet(&self) -> [LetterStatus; ALPHABET_LENGTH] {
+        let mut alphabet: [LetterStatus; ALPHABET_LENGTH] = [LetterStatus::Unknown; ALPHABET_LENGTH];
+        for (index, status) in alphabet.iter_mut().enumerate() {
+            *status = self.letter_status(letter(index));
+        }
+        alphabet
+    }
+
+    /// The first constraint `word` breaks, positions before counts.
+    ///
+    /// ```
+    /// use wordle::constraints::{Constraints, Violation};
+    /// use wordle::game::get_status;
+    ///
+    /// let mut constraints = Constraints::new(5);
+    /// constraints.add("TRACE", &get_status("TRACE", "CRANE"));
+    /// assert_eq!(constraints.check("BRACE"), Err(Violation::CannotBe { position: 4, letter: 'C' }));
+    /// assert_eq!(constraints.check("CRATE"), Err(Violation::TooMany { letter: 'T', max: 0 }));
+    /// assert_eq!(constraints.check("CRANE"), Ok(()));
+    /// ```
+    pub fn check(&self, word: &str) -> Result<(), Violation> {
+        for (position, c) in word.bytes().enumerate() {
+            if self.allowed[position][letter_index(c)] {
+                continue;
+            }
+            return Err(match self.greens[position] {
+                Some(green) => Violation::MustBe { position: position + 1, letter: green as char },
+                None => Violation::CannotBe { position: position + 1, letter: c as char },
+            });
+        }
+
+        let mut counts: [usize; ALPHABET_LENGTH] = [0; ALPHABET_LENGTH];
+        for c in word.bytes() {
+            counts[letter_index(c)] += 1;
+        }
+        for (index, &count) in counts.iter().enumerate() {
+            if count < self.min_count[index] {
+                return Err(Violation::TooFew { letter: letter(index), min: self.min_count[index] });
+            }
+            if count > self.max_count[index] {
+                return Err(Violation::TooMany { letter: letter(index), max: self.max_count[index] });
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether `word` could still be the answer.
+    pub fn allows(&self, word: &str) -> bool {
+        self.check(word).is_ok()
+    }
+
+    /// The difficult mode rule, weaker than `allows`: green letters stay in place and every letter found is used.
+    pub fn uses_hints(&self, word: &str) -> bool {
+        let greens_kept: bool = word.bytes().zip(&self.greens).all(|(c, green)| green.is_none_or(|green| c == green));
+        greens_kept && (0..ALPHABET_LENGTH).all(|index| self.min_count[index] == 0 || word.contains(letter(index)))
+    }
+
+    /// Whether `word` may be guessed next: any word in normal mode, one that `uses_hints` in difficult mode
+    /// and one the constraints `allows` in strict mode.
+    pub fn may_guess(&self, word: &str, difficult: bool, strict: bool) -> bool {
+        if strict { self.allows(word) } else { !difficult || self.uses_hints(word) }
+    }
+}
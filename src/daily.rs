@@ -0,0 +1,40 @@
+use std::fmt;
+use chrono::{Local, NaiveDate};
+
+use crate::game::Game;
+
+/// The date of Wordle 0 unless configured otherwise, the day the original game started counting.
+pub fn default_epoch() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2021, 6, 19).unwrap()
+}
+
+/// The puzzle of one calendar date, numbered by the days since the epoch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Daily {
+    pub date: NaiveDate,
+    pub number: usize,
+}
+
+impl Daily {
+    /// The puzzle of `date`; `None` if it is before `epoch`.
+    pub fn new(date: NaiveDate, epoch: NaiveDate) -> Option<Self> {
+        let days: i64 = date.signed_duration_since(epoch).num_days();
+        (days >= 0).then_some(Daily { date, number: days as usize })
+    }
+
+    /// The puzzle of the local date.
+    pub fn today(epoch: NaiveDate) -> Option<Self> {
+        Self::new(Local::now().date_naive(), epoch)
+    }
+
+    /// Like random mode with `seed`, the day index wrapping around once every answer has had its turn.
+    pub fn answer(&self, word_list: &[String], seed: u64) -> String {
+        Game::get_seed_random_word(word_list, seed, self.number % word_list.len() + 1)
+    }
+}
+
+impl fmt::Display for Daily {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Wordle {}", self.number)
+    }
+}
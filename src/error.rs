@@ -0,0 +1,151 @@
+use std::{
+    error::Error,
+    fmt,
+    io,
+    path::PathBuf,
+};
+
+/// Problems found while loading a word list; lines are counted from 1.
+#[derive(Debug)]
+pub enum WordListError {
+    MissingFile { path: PathBuf },
+    Unreadable { path: PathBuf, source: io::Error },
+    MultipleWords { path: PathBuf, line: usize },
+    WrongLength { path: PathBuf, line: usize, word: String, expected: usize },
+    NonAlphabetic { path: PathBuf, line: usize, word: String },
+    Duplicated { path: PathBuf, line: usize, word: String, first_line: usize },
+    NotSubset { path: PathBuf, line: usize, word: String, acceptable_path: PathBuf },
+}
+
+impl fmt::Display for WordListError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingFile { path } => write!(f, "{}: word list does not exist", path.display()),
+            Self::Unreadable { path, source } => write!(f, "{}: failed to read word list: {}", path.display(), source),
+            Self::MultipleWords { path, line } => write!(f, "{}:{}: there can be only one word in a line", path.display(), line),
+            Self::WrongLength { path, line, word, expected } => write!(f, "{}:{}: \"{}\" should be {} letters long", path.display(), line, word, expected),
+            Self::NonAlphabetic { path, line, word } => write!(f, "{}:{}: \"{}\" contains non-alphabetic characters", path.display(), line, word),
+            Self::Duplicated { path, line, word, first_line } => write!(f, "{}:{}: \"{}\" is duplicated, first seen on line {}", path.display(), line, word, first_line),
+            Self::NotSubset { path, line, word, acceptable_path } => write!(f, "{}:{}: \"{}\" is not in the acceptable list {}", path.display(), line, word, acceptable_path.display()),
+        }
+    }
+}
+
+impl Error for WordListError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Unreadable { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// Problems found while loading a config file.
+#[derive(Debug)]
+pub enum ConfigError {
+    MissingFile { path: PathBuf },
+    Unreadable { path: PathBuf, source: io::Error },
+    InvalidJson { path: PathBuf, line: usize, column: usize, message: String },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingFile { path } => write!(f, "{}: config file does not exist", path.display()),
+            Self::Unreadable { path, source } => write!(f, "{}: failed to read config file: {}", path.display(), source),
+            Self::InvalidJson { path, line, column, message } => write!(f, "{}:{}:{}: invalid config: {}", path.display(), line, column, message),
+        }
+    }
+}
+
+impl Error for ConfigError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Unreadable { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// Problems found while reading a state file or bringing it up to date.
+#[derive(Debug)]
+pub enum StateError {
+    Unreadable { path: PathBuf, source: io::Error },
+    Unwritable { path: PathBuf, source: io::Error },
+    InvalidJson { path: PathBuf, line: usize, column: usize, message: String },
+    UnknownVersion { path: PathBuf, version: String },
+    NewerVersion { path: PathBuf, version: u64, supported: u64 },
+    InvalidLayout { path: PathBuf, message: String },
+    ImpossibleGame { path: PathBuf, game: String, problem: String }, // a well formed game that cannot have been played
+}
+
+impl fmt::Display for StateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unreadable { path, source } => write!(f, "{}: failed to read state file: {}", path.display(), source),
+            Self::Unwritable { path, source } => write!(f, "{}: failed to save state file: {}", path.display(), source),
+            Self::InvalidJson { path, line, column, message } => write!(f, "{}:{}:{}: invalid state: {}", path.display(), line, column, message),
+            Self::UnknownVersion { path, version } => write!(f, "{}: {} is not a schema version", path.display(), version),
+            Self::NewerVersion { path, version, supported } => write!(f, "{}: schema version {} is newer than {}, the latest this version knows", path.display(), version, supported),
+            Self::InvalidLayout { path, message } => write!(f, "{}: invalid state: {}", path.display(), message),
+            Self::ImpossibleGame { path, game, problem } => write!(f, "{}: {} cannot have been played: {}", path.display(), game, problem),
+        }
+    }
+}
+
+impl StateError {
+    /// Whether the file holds something other than a state, as left by a crash, rather than a state this version
+    /// cannot read, a state with a game that cannot have been played or a file that cannot be read at all;
+    /// only then is the backup worth reading instead.
+    pub fn is_damage(&self) -> bool {
+        matches!(self, Self::InvalidJson { .. } | Self::InvalidLayout { .. })
+    }
+}
+
+impl Error for StateError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Unreadable { source, .. } | Self::Unwritable { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// ` of NAME` to tell whose game a message is about, nothing for the games played without a name.
+pub fn of_player(player: Option<&str>) -> String {
+    player.map_or(String::new(), |player| format!(" of {}", player))
+}
+
+/// Problems with wherever the history is kept.
+#[derive(Debug)]
+pub enum StoreError {
+    State(StateError),
+    Database { path: PathBuf, source: rusqlite::Error },
+    ImpossibleGame { path: PathBuf, game: String, problem: String }, // like "game 2 of Ada"
+}
+
+impl fmt::Display for StoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::State(error) => error.fmt(f),
+            Self::Database { path, source } => write!(f, "{}: database error: {}", path.display(), source),
+            Self::ImpossibleGame { path, game, problem } => write!(f, "{}: {} cannot have been played: {}", path.display(), game, problem),
+        }
+    }
+}
+
+impl Error for StoreError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::State(error) => error.source(),
+            Self::Database { source, .. } => Some(source),
+            Self::ImpossibleGame { .. } => None,
+        }
+    }
+}
+
+impl From<StateError> for StoreError {
+    fn from(error: StateError) -> Self {
+        Self::State(error)
+    }
+}
@@ -0,0 +1,184 @@
+use std::{fmt, io::{self, Write}, str::FromStr};
+
+use chrono::{DateTime, Local};
+use serde::Serialize;
+
+use crate::game::{Game, GuessWordStatus, LetterStatus};
+use crate::stats::Record;
+
+/// How exported rows are written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// A header line, then one comma separated line per row.
+    Csv,
+    /// One JSON object per line.
+    Jsonl,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name.to_lowercase().as_str() {
+            "csv" => Ok(Self::Csv),
+            "jsonl" => Ok(Self::Jsonl),
+            _ => Err(format!("unknown format \"{}\", expected csv or jsonl", name)),
+        }
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Csv => write!(f, "csv"),
+            Self::Jsonl => write!(f, "jsonl"),
+        }
+    }
+}
+
+/// A row that can be written as CSV as well as JSON.
+pub trait Row: Serialize {
+    const HEADER: &'static [&'static str];
+
+    /// The CSV fields, in the order of `HEADER`.
+    fn fields(&self) -> Vec<String>;
+}
+
+/// One stored game.
+#[derive(Serialize)]
+pub struct GameRow {
+    pub game: usize, // counting from 1
+    pub answer: String,
+    pub guesses: Vec<String>,
+    pub tries: usize,
+    pub won: bool,
+    pub mode: &'static str,
+    pub started_at: Option<DateTime<Local>>,
+}
+
+impl Row for GameRow {
+    const HEADER: &'static [&'static str] = &["game", "answer", "guesses", "tries", "won", "mode", "started_at"];
+
+    fn fields(&self) -> Vec<String> {
+        vec![
+            self.game.to_string(),
+            self.answer.clone(),
+            self.guesses.join(" "),
+            self.tries.to_string(),
+            self.won.to_string(),
+            self.mode.to_string(),
+            self.started_at.map_or(String::new(), |started_at| started_at.to_rfc3339()),
+        ]
+    }
+}
+
+/// One guess of a stored game, with the feedback it got.
+#[derive(Serialize)]
+pub struct GuessRow {
+    pub game: usize, // counting from 1
+    pub turn: usize, // counting from 1
+    pub answer: String,
+    pub guess: String,
+    pub pattern: String, // G, Y or R for each letter
+    pub greens: usize,
+    pub yellows: usize,
+}
+
+impl Row for GuessRow {
+    const HEADER: &'static [&'static str] = &["game", "turn", "answer", "guess", "pattern", "greens", "yellows"];
+
+    fn fields(&self) -> Vec<String> {
+        vec![
+            self.game.to_string(),
+            self.turn.to_string(),
+            self.answer.clone(),
+            self.guess.clone(),
+            self.pattern.clone(),
+            self.greens.to_string(),
+            self.yellows.to_string(),
+        ]
+    }
+}
+
+/// One row for each of `games`, numbered from 1.
+pub fn game_rows(games: &[Record]) -> Vec<GameRow> {
+    games
+        .iter()
+        .enumerate()
+        .map(|(i, record)| GameRow {
+            game: i + 1,
+            answer: record.answer.clone(),
+            guesses: record.guesses.clone(),
+            tries: record.guesses.len(),
+            won: record.is_win(),
+            mode: record.mode(),
+            started_at: record.started_at,
+        })
+        .collect()
+}
+
+/// One row for every guess of `games`, with the feedback the game gave it.
+///
+/// ```
+/// use wordle::export::guess_rows;
+/// use wordle::stats::Stats;
+///
+/// let stats = Stats::load(Some(wordle::store::open("tests/data/20_profiles.json".as_ref(), None).unwrap()), Some("Ada")).unwrap();
+/// let rows = guess_rows(stats.get_records().unwrap());
+/// assert_eq!((rows[0].guess.as_str(), rows[0].pattern.as_str(), rows[0].greens, rows[0].yellows), ("AUDIO", "RRRRG", 1, 0));
+/// ```
+pub fn guess_rows(games: &[Record]) -> Vec<GuessRow> {
+    let mut rows: Vec<GuessRow> = Vec::new();
+    for (i, record) in games.iter().enumerate() {
+        let game: Game = Game::new(record.answer.clone(), record.difficult, record.max_attempts(), Vec::new());
+        for (turn, guess) in record.guesses.iter().enumerate() {
+            let status: GuessWordStatus = game.get_guess_word_status(guess);
+            let count = |wanted: LetterStatus| status.iter().filter(|&&letter| letter == wanted).count();
+            rows.push(GuessRow {
+                game: i + 1,
+                turn: turn + 1,
+                answer: record.answer.clone(),
+                guess: guess.clone(),
+                pattern: status
+                    .iter()
+                    .map(|letter| match letter {
+                        LetterStatus::Green => 'G',
+                        LetterStatus::Yellow => 'Y',
+                        _ => 'R',
+                    })
+                    .collect(),
+                greens: count(LetterStatus::Green),
+                yellows: count(LetterStatus::Yellow),
+            });
+        }
+    }
+    rows
+}
+
+/// Writes `rows` to `out` in `format`.
+pub fn write<R: Row>(out: &mut impl Write, rows: &[R], format: Format) -> io::Result<()> {
+    match format {
+        Format::Csv => {
+            writeln!(out, "{}", R::HEADER.join(","))?;
+            for row in rows {
+                writeln!(out, "{}", row.fields().iter().map(|field| csv_field(field)).collect::<Vec<String>>().join(","))?;
+            }
+        }
+        Format::Jsonl => {
+            for row in rows {
+                serde_json::to_writer(&mut *out, row)?;
+                writeln!(out)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+// quoted if it would otherwise be split or misread
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
@@ -0,0 +1,133 @@
+use crate::analysis::GuessAnalysis;
+use crate::assist::{AssistError, Assistant, Suggestion};
+use crate::game::{ErrorType, Game, GuessOutcome};
+use crate::stats::Stats;
+
+/// Where answers, guesses and decisions come from.
+pub trait InputSource {
+    /// The next typed answer; `None` once the player quits or the input runs out.
+    fn read_answer(&mut self) -> Option<String>;
+
+    /// The next typed guess for `game`; `None` once the player quits or the input runs out.
+    fn read_guess(&mut self, game: &Game) -> Option<String>;
+
+    /// Whether the player wants another game.
+    fn ask_continue(&mut self) -> bool;
+
+    /// Whether the player wants recommendations on top of the remaining candidates.
+    fn ask_recommend(&mut self) -> bool {
+        false
+    }
+}
+
+/// Where the game is shown. Only rejected input and the latest guess are required, the rest is optional.
+pub trait Renderer {
+    /// A new game is about to start.
+    fn render_start(&mut self, _game: &Game) {}
+
+    /// A typed answer or guess was rejected.
+    fn render_invalid(&mut self, error: &ErrorType);
+
+    /// The latest entry of `game.guesses` was accepted.
+    fn render_guess(&mut self, game: &Game);
+
+    /// Words still consistent with every guess so far, shown in hint mode.
+    fn render_candidates(&mut self, _candidates: &[String]) {}
+
+    /// Best next guesses, shown when the player asks for them in hint mode.
+    fn render_recommendations(&mut self, _words: &[String]) {}
+
+    /// `game` has been won or lost.
+    fn render_game_over(&mut self, _game: &Game) {}
+
+    /// How every guess of a finished game compared with the recommendations, shown when the player asked for it.
+    fn render_analysis(&mut self, _analysis: &[GuessAnalysis]) {}
+
+    /// The result block of a finished game, shown when the player asked to share it.
+    fn render_share(&mut self, _text: &str) {}
+
+    /// Statistics after a finished game has been recorded.
+    fn render_stats(&mut self, _stats: &Stats) {}
+}
+
+/// Where `wordle assist` takes guesses made in another game from, and shows what to guess next.
+pub trait AssistFrontend {
+    /// The next typed line, a guess and its feedback or `undo`; `None` once the player quits or the input runs out.
+    fn read_entry(&mut self, assistant: &Assistant) -> Option<String>;
+
+    /// A typed line was not taken.
+    fn render_entry_error(&mut self, error: &AssistError);
+
+    /// The guesses so far, the `candidates` they leave and `suggestions` for the next one.
+    fn render_assistant(&mut self, assistant: &Assistant, candidates: &[String], suggestions: &[Suggestion]);
+}
+
+/// Takes guesses and their feedback until the answer is found or the input runs out.
+pub fn assist<F: AssistFrontend + ?Sized>(frontend: &mut F, assistant: &mut Assistant) {
+    loop {
+        let candidates: Vec<String> = assistant.candidates();
+        let suggestions: Vec<Suggestion> = assistant.suggestions(&candidates);
+        frontend.render_assistant(assistant, &candidates, &suggestions);
+        if assistant.is_solved() {
+            return;
+        }
+        loop {
+            let Some(typed) = frontend.read_entry(assistant) else {
+                return;
+            };
+            let entered: Result<(), AssistError> = if typed.trim().eq_ignore_ascii_case("undo") { assistant.undo() } else { assistant.enter(&typed) };
+            match entered {
+                Ok(()) => break,
+                Err(error) => frontend.render_entry_error(&error),
+            }
+        }
+    }
+}
+
+/// Asks for answers until one of `word_list` is typed; `None` if the input ran out first.
+pub fn read_answer<F: InputSource + Renderer + ?Sized>(frontend: &mut F, word_list: &[String], word_length: usize) -> Option<String> {
+    loop {
+        let typed: String = frontend.read_answer()?;
+        match Game::check_answer(&typed, word_list, word_length) {
+            Ok(answer) => return Some(answer),
+            Err(error) => frontend.render_invalid(&error),
+        }
+    }
+}
+
+/// Plays `game` until it is won or lost; `false` if the input ran out first.
+pub fn play_round<F: InputSource + Renderer + ?Sized>(frontend: &mut F, game: &mut Game, word_list: &[String], hint_mode: bool) -> bool {
+    frontend.render_start(game);
+    loop {
+        let typed: String = match frontend.read_guess(game) {
+            Some(typed) => typed,
+            None => return false,
+        };
+        match game.guess(&typed, word_list) {
+            GuessOutcome::Invalid(error) => frontend.render_invalid(&error),
+            GuessOutcome::Continue(_) => {
+                frontend.render_guess(game);
+                if hint_mode {
+                    show_hints(frontend, game);
+                }
+            }
+            GuessOutcome::Won | GuessOutcome::Lost => {
+                frontend.render_guess(game);
+                frontend.render_game_over(game);
+                return true;
+            }
+        }
+    }
+}
+
+// the candidates left by the feedback so far, and recommendations if asked for
+fn show_hints<F: InputSource + Renderer + ?Sized>(frontend: &mut F, game: &mut Game) {
+    let candidates: Vec<String> = game.get_hint();
+    frontend.render_candidates(&candidates);
+    game.hints_shown += 1;
+    if frontend.ask_recommend() {
+        game.recommendations_shown += 1;
+        let recommended: Vec<String> = game.get_recommend_words(&candidates);
+        frontend.render_recommendations(&recommended);
+    }
+}
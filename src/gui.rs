@@ -1,315 +1,397 @@
-use fltk::{app, button::Button, frame::{*}, prelude::*, window::*};
+use fltk::{app, button::{Button, CheckButton}, frame::Frame, input::Input, prelude::*, window::*};
 use fltk::enums::FrameType;
-use fltk::enums::Color;
+use fltk::enums::{Align, Color};
+use fltk::dialog::{alert, choice2_default, input_default, message_default};
 use std::{cell::RefCell, process::exit};
 use std::rc::Rc;
-use crate::game::{Game, GuessWordStatus, LetterStatus};
-use fltk::dialog::alert;
+use std::thread;
 use std::collections::HashMap;
+use chrono::{Local, Timelike};
+use wordle::analysis::GuessAnalysis;
+use wordle::frontend::{InputSource, Renderer};
+use wordle::game::{ErrorType, Game, LetterStatus};
+use wordle::stats::{Distribution, Standing, Stats};
 
-// a round of game(max attempts = 6)
-pub fn run_gui(name: String,  answer_list: Vec<String>, seed: u64, day: usize, difficult_mode: bool, acceptable_list: Vec<String>) -> bool{
-    let letter_to_number: HashMap<char, (usize, usize)> = create_letter_to_number_map();
-    let app = app::App::default();
-    let wind: Rc<RefCell<DoubleWindow>> = Rc::new(RefCell::new(DoubleWindow::new(0, 0, 800, 800, "")));
-    
+const KEYBOARD_ROWS: [&str; 3] = ["QWERTYUIOP", "ASDFGHJKL", "ZXCVBNM"];
+
+// what the buttons send to the event loop
+#[derive(Clone, Copy)]
+enum Message {
+    Letter(char),
+    Enter,
+    Delete,
+    Quit,
+}
+
+// ask for the player's name and the difficulty, `None` if the window is closed
+pub fn home_page(app: &app::App) -> Option<(String, bool)> {
+    let wind: Rc<RefCell<DoubleWindow>> = Rc::new(RefCell::new(DoubleWindow::new(0, 0, 800, 800, "Home Page")));
     wind.borrow_mut().set_pos(400, 0);
-    let wind_clone: Rc<RefCell<DoubleWindow>> = Rc::clone(&wind);
-    wind_clone.borrow_mut().set_color(Color::White);
-    wind_clone.borrow_mut().set_label(&format!("Wordle in Rust - Hello, {}!", name));  
+    wind.borrow_mut().set_color(Color::White);
+    let input = Input::new(180, 200, 120, 30, "Name");
+
+    let mut button = Button::new(180, 260, 120, 30, "Start");
+    button.set_color(Color::rgb_color(156, 34, 24));
+    button.set_frame(FrameType::FlatBox);
+    button.set_label_size(20);
+    button.set_label_color(Color::White);
+
+    let mut exit_button = Button::new(180, 320, 120, 30, "exit");
+    exit_button.set_color(Color::rgb_color(106, 170, 100));
+    exit_button.set_frame(FrameType::FlatBox);
+    exit_button.set_label_size(20);
+    exit_button.set_label_color(Color::White);
 
-    let mut exit_button = Button::new(600, 410, 80, 40, "exit");
-    exit_button.set_color(Color::rgb_color(106, 170, 100)); 
-    exit_button.set_frame(FrameType::FlatBox); 
-    exit_button.set_label_size(18); 
-    exit_button.set_label_color(Color::White); 
+    let mut frame = Frame::new(180, 750, 400, 30, "");
+    frame.set_frame(FrameType::FlatBox);
+    frame.set_color(Color::White);
+    frame.set_label_size(24);
+    frame.set_label_color(Color::Black);
+
+    let mut frame_time = Frame::new(180, 400, 160, 40, "");
+    frame_time.set_frame(FrameType::FlatBox);
+    frame_time.set_color(Color::White);
+    frame_time.set_label_size(24);
+    frame_time.set_label_color(Color::Black);
+
+    let mut difficult_check = CheckButton::new(400, 280, 40, 30, "Difficult Mode");
+    difficult_check.set_frame(FrameType::FlatBox);
+    difficult_check.set_label_size(16);
+    difficult_check.set_label_color(Color::Black);
+    difficult_check.set_color(Color::White);
+
+    thread::spawn(move || {
+        let current_time = Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+        let current_hour = Local::now().hour();
+        if (0..8).contains(&current_hour) || (23..25).contains(&current_hour) {
+            frame.set_label("Are you having trouble falling asleep? :/");
+        } else if (7 .. 9).contains(&current_hour) {
+            frame.set_label("You should have breakfast first :P");
+        } else if (9 .. 13).contains(&current_hour) {
+            frame.set_label("It's time to work :)");
+        } else if (13 .. 14).contains(&current_hour) {
+            frame.set_label("You should have lunch first :P");
+        } else if (14 .. 18).contains(&current_hour) {
+            frame.set_label("It's time to work :)");
+        } else if (18 .. 20).contains(&current_hour) {
+            frame.set_label("You should have dinner first :p");
+        } else {
+            frame.set_label("Take a break and go to bed early -_-zZ");
+        }
+        frame_time.set_label(&current_time);
+    });
 
-    
+    let started: Rc<RefCell<bool>> = Rc::new(RefCell::new(false));
+    let started_clone = Rc::clone(&started);
+    let wind_clone = Rc::clone(&wind);
+    button.set_callback(move |_| {
+        *started_clone.borrow_mut() = true;
+        wind_clone.borrow_mut().hide();
+    });
     exit_button.set_callback(move |_| {
         exit(0);
     });
 
-    let secret_word: String;
-    secret_word = Game::get_seed_random_word(&answer_list, seed, day);
-
-    let mut game: Game = Game::new(secret_word.to_string(), difficult_mode, acceptable_list.clone(), acceptable_list.clone());
-    
-    let attempts: Rc<RefCell<usize>> = Rc::new(RefCell::new(game.get_tries()));        
-    println!("Secret word: {}", secret_word);
-    println!("attempts: {}", attempts.borrow());
-
-    let mut x: i32;
-    let mut y = 50;
-
-    let mut vec_frames: Vec<Vec<Frame>> = Vec::new();
-    for _ in 0..6 {
-        x = 230;
-        let mut frames: Vec<Frame> = Vec::new();
-        for _ in 0..5 {
-            let mut frame = Frame::new(x, y, 50, 50, "");
-            frame.set_frame(FrameType::UpBox);
-            frame.set_label_size(20);
-            frame.set_label_color(Color::Black);
-            frame.set_color(Color::White); 
-            frames.push(frame);
-            x += 60;
-        }
-        vec_frames.push(frames);
-        y += 60;
+    wind.borrow_mut().end();
+    wind.borrow_mut().show();
+    app.run().unwrap();
 
+    let started: bool = *started.borrow();
+    started.then(|| (input.value(), difficult_check.is_checked()))
+}
+
+/// FLTK front end: an on-screen keyboard typing into a grid with one row per attempt.
+pub struct Gui {
+    app: app::App,
+    wind: DoubleWindow,
+    chart: DoubleWindow, // the statistics after a game, refilled every time
+    frames: Vec<Vec<Frame>>,
+    keys: HashMap<char, Button>,
+    sender: app::Sender<Message>,
+    receiver: app::Receiver<Message>,
+    typed: String,
+}
+
+impl Gui {
+    pub fn new(app: app::App, name: &str) -> Self {
+        let (sender, receiver) = app::channel::<Message>();
+        let mut wind: DoubleWindow = DoubleWindow::new(0, 0, 800, 800, "");
+        wind.set_pos(400, 0);
+        wind.set_color(Color::White);
+        wind.set_label(&format!("Wordle in Rust - Hello, {}!", name));
+        wind.end();
+        let mut chart: DoubleWindow = DoubleWindow::new(450, 150, 500, 400, "Statistics");
+        chart.set_color(Color::White);
+        chart.end();
+        Gui {
+            app,
+            wind,
+            chart,
+            frames: Vec::new(),
+            keys: HashMap::new(),
+            sender,
+            receiver,
+            typed: String::new(),
+        }
     }
 
-    let selected_letters = Rc::new(RefCell::new(String::new()));
-
-    let mut vec_buttons: Vec<Vec<Button>> = Vec::new();
-    
-    let mut buttons: Vec<Button> = Vec::new(); 
-    let mut x = 50;
-    let mut y = 550;
-
-    for c in ['Q', 'W', 'E', 'R', 'T', 'Y', 'U', 'I', 'O', 'P'].iter() {
-        let mut button = Button::new(x, y, 50, 50, c.to_string().as_str());
-        button.set_frame(FrameType::GtkUpBox);
-        button.set_label_size(20);
-        button.set_label_color(Color::Black);
-        button.set_color(Color::from_rgb(211, 214, 218));
-
-        let selected_letters_clone: Rc<RefCell<String>> = Rc::clone(&selected_letters);
-        let vec_frames_clone: Rc<RefCell<Vec<Vec<Frame>>>> = Rc::clone(&Rc::new(RefCell::new(vec_frames.clone())));
-        let attempts_clone: Rc<RefCell<usize>> = Rc::clone(&attempts);
-        button.set_callback(move |_| {
-            let mut selected_letters: std::cell::RefMut<'_, String> = selected_letters_clone.borrow_mut();
-            if selected_letters.len() < 5 {
-                selected_letters.push(*c);
-                println!("{}", selected_letters.clone());
-                let mut frames: std::cell::RefMut<'_, Vec<Vec<Frame>>> = vec_frames_clone.borrow_mut();
-                let attempts_clone: std::cell::Ref<'_, usize> = attempts_clone.borrow();
-                frames[*attempts_clone][selected_letters.len() - 1].set_label(c.to_string().as_str());
+    // a fresh grid for `word_length` letters and `max_attempts` rows, plus the keyboard
+    fn build(&mut self, word_length: usize, max_attempts: usize) {
+        self.wind.clear();
+        self.wind.begin();
+
+        let mut exit_button = Button::new(600, 410, 80, 40, "exit");
+        exit_button.set_color(Color::rgb_color(106, 170, 100));
+        exit_button.set_frame(FrameType::FlatBox);
+        exit_button.set_label_size(18);
+        exit_button.set_label_color(Color::White);
+        exit_button.emit(self.sender, Message::Quit);
+
+        // the grid has to fit above the keyboard, so shrink cells when there are many attempts
+        let cell: i32 = (480 / max_attempts as i32).min(60);
+        let mut y: i32 = 50;
+        self.frames = Vec::new();
+        for _ in 0..max_attempts {
+            let mut x: i32 = 375 - (cell * word_length as i32 - 10) / 2; // keep the row centered
+            let mut frames: Vec<Frame> = Vec::new();
+            for _ in 0..word_length {
+                let mut frame = Frame::new(x, y, cell - 10, cell - 10, "");
+                frame.set_frame(FrameType::UpBox);
+                frame.set_label_size(20);
+                frame.set_label_color(Color::Black);
+                frame.set_color(Color::White);
+                frames.push(frame);
+                x += cell;
             }
-        });
-        buttons.push(button);
-        x += 70; 
-    }
-    vec_buttons.push(buttons);
-    y += 70; 
-    x = 90;
-
-    buttons = Vec::new();
-    for c in ['A', 'S', 'D', 'F', 'G', 'H', 'J', 'K', 'L'].iter() {
-        let mut button = Button::new(x, y, 50, 50, c.to_string().as_str());
-        button.set_frame(FrameType::GtkUpBox);
-        button.set_label_size(20);
-        button.set_label_color(Color::Black);
-        button.set_color(Color::from_rgb(211, 214, 218));
-        let selected_letters_clone = Rc::clone(&selected_letters);
-        let vec_frames_clone: Rc<RefCell<Vec<Vec<Frame>>>> = Rc::clone(&Rc::new(RefCell::new(vec_frames.clone())));
-        let attempts_clone: Rc<RefCell<usize>> = Rc::clone(&attempts);
-        button.set_callback(move |_| {
-            let mut selected_letters = selected_letters_clone.borrow_mut();
-            if selected_letters.len() < 5 {
-                selected_letters.push(*c);
-                println!("{}", selected_letters.clone());
-                let mut frames: std::cell::RefMut<'_, Vec<Vec<Frame>>> = vec_frames_clone.borrow_mut();
-                let attempts_clone: std::cell::Ref<'_, usize> = attempts_clone.borrow();
-                frames[*attempts_clone][selected_letters.len() - 1].set_label(c.to_string().as_str());
+            self.frames.push(frames);
+            y += cell;
+        }
+
+        self.keys = HashMap::new();
+        let mut y: i32 = 550;
+        for (row, (letters, start)) in KEYBOARD_ROWS.iter().zip([50, 90, 160]).enumerate() {
+            let mut x: i32 = start;
+            for c in letters.chars() {
+                let mut button = Button::new(x, y, 50, 50, c.to_string().as_str());
+                button.set_frame(FrameType::GtkUpBox);
+                button.set_label_size(20);
+                button.set_label_color(Color::Black);
+                button.set_color(Color::from_rgb(211, 214, 218));
+                button.emit(self.sender, Message::Letter(c));
+                self.keys.insert(c, button);
+                x += 70;
             }
-        });
-        buttons.push(button);
-        x += 70; 
+            if row < KEYBOARD_ROWS.len() - 1 {
+                y += 70;
+            }
+        }
+
+        let mut enter_button: Button = Button::new(60, y, 80, 50, "ENTER");
+        let mut delete_button: Button = Button::new(650, y, 80, 50, "DELETE");
+        for (button, message) in [(&mut enter_button, Message::Enter), (&mut delete_button, Message::Delete)] {
+            button.set_frame(FrameType::GtkUpBox);
+            button.set_label_size(20);
+            button.set_label_color(Color::Black);
+            button.set_color(Color::from_rgb(211, 214, 218));
+            button.emit(self.sender, message);
+        }
+
+        self.wind.end();
+        self.wind.show();
+        self.wind.redraw();
+        self.typed.clear();
     }
+}
 
-    vec_buttons.push(buttons);  
-    y += 70;
-
-    let mut buttons: Vec<Button> = Vec::new();
-    let mut x = 160;
-    for c in ['Z', 'X', 'C', 'V', 'B', 'N', 'M'].iter() {
-        let mut button = Button::new(x, y, 50, 50, c.to_string().as_str());
-        button.set_frame(FrameType::GtkUpBox);
-        button.set_label_size(20);
-        button.set_label_color(Color::Black);
-        button.set_color(Color::from_rgb(211, 214, 218));
-        let selected_letters_clone = Rc::clone(&selected_letters);
-        let vec_frames_clone = Rc::clone(&Rc::new(RefCell::new(vec_frames.clone())));
-        let attempts_clone = Rc::clone(&attempts);
-        button.set_callback(move |_| {
-            let mut selected_letters = selected_letters_clone.borrow_mut();
-            if selected_letters.len() < 5 {
-                selected_letters.push(*c);
-                println!("{}", selected_letters.clone());
-                let mut frames = vec_frames_clone.borrow_mut();
-                let attempts_clone = attempts_clone.borrow();
-                frames[*attempts_clone][selected_letters.len() - 1].set_label(c.to_string().as_str());
-            }
-        });
-        buttons.push(button);
-        x += 70; 
+fn status_color(status: LetterStatus) -> Option<Color> {
+    match status {
+        LetterStatus::Green => Some(Color::rgb_color(106, 170, 100)),
+        LetterStatus::Yellow => Some(Color::rgb_color(201, 180, 88)),
+        LetterStatus::Red => Some(Color::rgb_color(156, 34, 24)),
+        LetterStatus::Unknown => None,
     }
-    vec_buttons.push(buttons);
-
-    let mut enter_button: Button = Button::new(60, y, 80, 50, "ENTER");
-    enter_button.set_frame(FrameType::GtkUpBox);
-    enter_button.set_label_size(20);
-    enter_button.set_label_color(Color::Black);
-    enter_button.set_color(Color::from_rgb(211, 214, 218));
-    let selected_letters_clone: Rc<RefCell<String>> = Rc::clone(&selected_letters);
-    let attempts_clone: Rc<RefCell<usize>> = Rc::clone(&attempts);
-    let vec_frames_clone: Rc<RefCell<Vec<Vec<Frame>>>> = Rc::clone(&Rc::new(RefCell::new(vec_frames.clone())));
-    let vec_buttons_clone: Rc<RefCell<Vec<Vec<Button>>>> = Rc::clone(&Rc::new(RefCell::new(vec_buttons.clone())));
-    
-    let wind_clone_for_restart: Rc<RefCell<DoubleWindow>> = Rc::clone(&wind);
-
-    enter_button.set_callback(move |_| {
-        let mut selected_letters = selected_letters_clone.borrow_mut();
-        if selected_letters.len() == 5 {
-            if !Game::input_valid_check(&selected_letters.to_uppercase(), &acceptable_list.clone()) {
-                alert(550, 200, "Not in word list");
-            } 
-            else if difficult_mode && !game.check_difficult_mode(&selected_letters.to_uppercase()) {
-                alert(550, 200, "Invalid in difficult mode");
-            }
-            
-            else { //valid word
-                let result: GuessWordStatus = game.play(&selected_letters.to_uppercase());
-                let mut attempts_clone: std::cell::RefMut<'_, usize> = attempts_clone.borrow_mut(); 
-                *attempts_clone += 1;
-                if *attempts_clone == 6 || selected_letters.to_uppercase() == secret_word.clone() {
-                    let mut message: String = " ".to_string();
-
-                    if selected_letters.to_uppercase() == secret_word.clone() {
-                        message = "You win!".to_string();
-                    }
-                    else if *attempts_clone == 6 && selected_letters.to_uppercase() != secret_word.clone(){
-                        message = "You lose!".to_string();
-                    }
-                    let wind_: Rc<RefCell<DoubleWindow>> = Rc::clone(&wind);                    
-                    let mut wind_: std::cell::RefMut<'_, DoubleWindow> = wind_.borrow_mut();
-            
-                    wind_.set_label(&message); 
-                    wind_.clear();
-                    wind_.redraw();
-                    let mut resart_button = Button::new(160, 150, 200, 100, "RESTART");
-                    resart_button.set_frame(FrameType::GtkUpBox);
-                    resart_button.set_label_size(20);
-                    resart_button.set_label_color(Color::White);
-                    resart_button.set_color(Color::rgb_color(156, 34, 24));
-    
-                    let wind_clone_for_restart_inner = Rc::clone(&wind_clone_for_restart);
-                    resart_button.set_callback(move |_| {
-                        wind_clone_for_restart_inner.borrow_mut().hide();
-                        true;
-                    });
-                    let mut close_button = Button::new(430, 150, 200, 100, "CLOSE");
-                    close_button.set_frame(FrameType::GtkUpBox);
-                    close_button.set_label_size(20);
-                    close_button.set_label_color(Color::White);
-                    close_button.set_color(Color::rgb_color(106, 170, 100));
-    
-                    close_button.set_callback(move |_| {
-                        exit(0);
-                    });
-                    wind_.add(&resart_button);
-                    wind_.add(&close_button);
+}
+
+impl InputSource for Gui {
+    fn read_answer(&mut self) -> Option<String> {
+        input_default("Please type in the answer to start the game", "")
+    }
+
+    fn read_guess(&mut self, game: &Game) -> Option<String> {
+        let row: usize = game.get_tries();
+        while self.app.wait() {
+            match self.receiver.recv() {
+                Some(Message::Letter(c)) if self.typed.len() < game.word_length() => {
+                    self.typed.push(c);
+                    self.frames[row][self.typed.len() - 1].set_label(&c.to_string());
                 }
-                else {
-                    let mut frames = vec_frames_clone.borrow_mut();
-                    let mut buttons_clone = vec_buttons_clone.borrow_mut();
-
-                    for i in 0..5 {
-                        match result[i] {
-                            LetterStatus::Green => {
-                                frames[*attempts_clone - 1][i].set_color(Color::rgb_color(106, 170, 100));
-                                let letter = selected_letters.chars().nth(i).unwrap();
-                                if let Some(&(row, column)) = letter_to_number.get(&letter) {
-                                    buttons_clone[row][column].set_color(Color::rgb_color(106, 170, 100));
-                                    buttons_clone[row][column].set_label_color(Color::White);
-                                    buttons_clone[row][column].set_label(&selected_letters.chars().nth(i).unwrap().to_string());
-                                } else {}
-                            },
-                            LetterStatus::Yellow => {
-                                frames[*attempts_clone - 1][i].set_color(Color::rgb_color(201, 180, 88));
-                                let letter = selected_letters.chars().nth(i).unwrap();
-                                if let Some(&(row, column)) = letter_to_number.get(&letter) {
-                                    buttons_clone[row][column].set_color(Color::rgb_color(201, 180, 88));
-                                    buttons_clone[row][column].set_label_color(Color::White);
-                                    buttons_clone[row][column].set_label(&selected_letters.chars().nth(i).unwrap().to_string());
-                                } else {}
-                            },
-                            LetterStatus::Red => {
-                                frames[*attempts_clone - 1][i].set_color(Color::rgb_color(156, 34, 24));
-                                let letter = selected_letters.chars().nth(i).unwrap();
-                                if let Some(&(row, column)) = letter_to_number.get(&letter) {
-                                    buttons_clone[row][column].set_color(Color::rgb_color(156, 34, 24));
-                                    buttons_clone[row][column].set_label_color(Color::White);
-                                    buttons_clone[row][column].set_label(&selected_letters.chars().nth(i).unwrap().to_string());
-                                } else {}
-                            },
-                            _ => {} 
-                        }
-                        frames[*attempts_clone - 1][i].set_label_color(Color::White);
-                        frames[*attempts_clone - 1][i].set_label(&selected_letters.chars().nth(i).unwrap().to_string());
-                    }
+                Some(Message::Delete) if !self.typed.is_empty() => {
+                    self.typed.pop();
+                    self.frames[row][self.typed.len()].set_label("");
                 }
-                selected_letters.clear();
-            }                
-            println!("{}", selected_letters.clone());
-        }
-    });            
-
-    let mut delete_button = Button::new(650, y, 80, 50, "DELETE");
-    delete_button.set_frame(FrameType::GtkUpBox);
-    delete_button.set_label_size(20);
-    delete_button.set_label_color(Color::Black);
-    delete_button.set_color(Color::from_rgb(211, 214, 218));
-
-    let selected_letters_clone = Rc::clone(&selected_letters);
-    let vec_frames_clone = Rc::clone(&Rc::new(RefCell::new(vec_frames.clone())));
-    let attempts_clone = Rc::clone(&attempts);
-    delete_button.set_callback(move |_| {
-        let mut selected_letters = selected_letters_clone.borrow_mut();
-        if !selected_letters.is_empty() {
-            let mut frames = vec_frames_clone.borrow_mut();
-            let attempts_clone = attempts_clone.borrow();
-            frames[*attempts_clone][selected_letters.len() - 1].set_label(' '.to_string().as_str());
-    
-            selected_letters.pop();
-            println!("{}", selected_letters.clone());
+                Some(Message::Enter) if self.typed.len() == game.word_length() => return Some(self.typed.clone()),
+                Some(Message::Quit) => return None,
+                _ => {}
+            }
         }
-    });
+        None
+    }
 
-    wind_clone.borrow_mut().end();
-    wind_clone.borrow_mut().show();
-    app.run().unwrap();
-    true
+    fn ask_continue(&mut self) -> bool {
+        choice2_default("Would you like to start a new game?", "CLOSE", "RESTART", "") == Some(1)
+    }
 }
 
-fn create_letter_to_number_map() -> HashMap<char, (usize, usize)> {
-    let mut letter_to_number = HashMap::new();
-    letter_to_number.insert('Q', (0, 0));
-    letter_to_number.insert('W', (0, 1));
-    letter_to_number.insert('E', (0, 2));
-    letter_to_number.insert('R', (0, 3));
-    letter_to_number.insert('T', (0, 4));
-    letter_to_number.insert('Y', (0, 5));
-    letter_to_number.insert('U', (0, 6));
-    letter_to_number.insert('I', (0, 7));
-    letter_to_number.insert('O', (0, 8));
-    letter_to_number.insert('P', (0, 9));   
-    letter_to_number.insert('A', (1, 0));
-    letter_to_number.insert('S', (1, 1));
-    letter_to_number.insert('D', (1, 2));
-    letter_to_number.insert('F', (1, 3));
-    letter_to_number.insert('G', (1, 4));
-    letter_to_number.insert('H', (1, 5));
-    letter_to_number.insert('J', (1, 6));
-    letter_to_number.insert('K', (1, 7));
-    letter_to_number.insert('L', (1, 8));
-    letter_to_number.insert('Z', (2, 0));
-    letter_to_number.insert('X', (2, 1));
-    letter_to_number.insert('C', (2, 2));
-    letter_to_number.insert('V', (2, 3));
-    letter_to_number.insert('B', (2, 4));
-    letter_to_number.insert('N', (2, 5));
-    letter_to_number.insert('M', (2, 6));
-    letter_to_number
-}
\ No newline at end of file
+impl Renderer for Gui {
+    fn render_start(&mut self, game: &Game) {
+        self.build(game.word_length(), game.max_attempts);
+        if let Some(daily) = &game.daily {
+            self.wind.set_label(&daily.to_string());
+        }
+    }
+
+    fn render_invalid(&mut self, error: &ErrorType) {
+        let message: String = match error {
+            ErrorType::HintUnused => "Invalid in difficult mode".to_string(),
+            ErrorType::Inconsistent(violation) => violation.to_string(),
+            _ => "Not in word list".to_string(),
+        };
+        alert(550, 200, &message);
+    }
+
+    fn render_guess(&mut self, game: &Game) {
+        let row: usize = game.get_tries() - 1;
+        let (word, status) = &game.guesses[row];
+        for (i, letter) in word.chars().enumerate() {
+            let frame: &mut Frame = &mut self.frames[row][i];
+            if let Some(color) = status_color(status[i]) {
+                frame.set_color(color);
+            }
+            frame.set_label_color(Color::White);
+            frame.set_label(&letter.to_string());
+        }
+        for (letter, button) in self.keys.iter_mut() {
+            if let Some(color) = status_color(game.constraints.letter_status(*letter)) {
+                button.set_color(color);
+                button.set_label_color(Color::White);
+            }
+        }
+        self.typed.clear();
+        self.wind.redraw();
+    }
+
+    fn render_game_over(&mut self, game: &Game) {
+        if game.is_win {
+            self.wind.set_label("You win!");
+        } else {
+            self.wind.set_label(&format!("You lose! The answer is {}", game.answer));
+        }
+    }
+
+    fn render_share(&mut self, text: &str) {
+        message_default(text);
+    }
+
+    fn render_analysis(&mut self, analysis: &[GuessAnalysis]) {
+        let lines: Vec<String> = analysis
+            .iter()
+            .enumerate()
+            .map(|(i, guess)| {
+                format!(
+                    "{}. {}: {} to {} candidates, {:.2} bits ({:+.2} luck), best {} ({:.0}% skill)",
+                    i + 1,
+                    guess.guess,
+                    guess.before,
+                    guess.after,
+                    guess.bits,
+                    guess.luck(),
+                    guess.best,
+                    guess.skill() * 100.0
+                )
+            })
+            .collect();
+        message_default(&lines.join("\n"));
+    }
+
+    // a bar chart of the guess distribution in the statistics window, closed with OK
+    fn render_stats(&mut self, stats: &Stats) {
+        let distribution: Distribution = stats.get_distribution();
+        let mut lines: Vec<String> = stats
+            .get_mode_breakdown()
+            .into_iter()
+            .map(|(mode, summary)| (mode.to_string(), summary))
+            .chain(stats.get_word_list_breakdown())
+            .map(|(name, summary)| format!("{}: {} played, {:.0}% won", name, summary.games(), summary.success_rate() * 100.0))
+            .collect();
+        lines.insert(0, format!("Average guesses {:.2}", stats.get_average_tries()));
+        let standings: Vec<Standing> = stats.get_leaderboard();
+        if !standings.is_empty() {
+            lines.push("Leaderboard:".to_string());
+        }
+        lines.extend(standings.iter().enumerate().map(|(i, standing)| {
+            format!(
+                "{}. {}: {:.0}% won, {:.2} guesses, streak {} (max {})",
+                i + 1,
+                standing.player,
+                standing.summary.success_rate() * 100.0,
+                standing.summary.average_tries(),
+                standing.current_streak,
+                standing.max_streak
+            )
+        }));
+        let bars: usize = distribution.wins.len() + 1;
+        let height: i32 = 150 + 40 * bars as i32 + 30 * lines.len() as i32;
+        self.chart.clear();
+        self.chart.set_size(500, height);
+        self.chart.begin();
+
+        let mut header = Frame::new(20, 20, 460, 40, "");
+        header.set_label(&format!(
+            "Won {} of {}, current streak {}, max streak {}",
+            stats.get_wins(),
+            stats.get_wins() + stats.get_fails(),
+            stats.get_current_streak(),
+            stats.get_max_streak()
+        ));
+        header.set_label_size(18);
+
+        let most: usize = distribution.wins.iter().copied().fold(distribution.fails, usize::max).max(1);
+        let mut y: i32 = 80;
+        let counts = distribution.wins.iter().enumerate().map(|(i, &count)| ((i + 1).to_string(), count, LetterStatus::Green));
+        for (label, count, status) in counts.chain([("X".to_string(), distribution.fails, LetterStatus::Red)]) {
+            let mut name = Frame::new(20, y, 30, 30, "");
+            name.set_label(&label);
+            name.set_label_size(18);
+            let mut bar = Frame::new(60, y, 40 + (380 * count / most) as i32, 30, "");
+            bar.set_frame(FrameType::FlatBox);
+            bar.set_color(status_color(status).unwrap());
+            bar.set_label(&count.to_string());
+            bar.set_label_color(Color::White);
+            bar.set_align(Align::Right | Align::Inside);
+            y += 40;
+        }
+        for line in &lines {
+            let mut frame = Frame::new(20, y, 460, 30, "");
+            frame.set_label(line);
+            frame.set_label_size(16);
+            frame.set_align(Align::Left | Align::Inside);
+            y += 30;
+        }
+
+        let mut ok_button = Button::new(210, y + 20, 80, 40, "OK");
+        ok_button.set_color(Color::rgb_color(106, 170, 100));
+        ok_button.set_frame(FrameType::FlatBox);
+        ok_button.set_label_size(18);
+        ok_button.set_label_color(Color::White);
+        ok_button.emit(self.sender, Message::Enter);
+        self.chart.end();
+        self.chart.show();
+
+        while self.app.wait() {
+            if let Some(Message::Enter | Message::Quit) = self.receiver.recv() {
+                break;
+            }
+        }
+        self.chart.hide();
+    }
+}
@@ -0,0 +1,192 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    io,
+    mem,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{of_player, StateError, StoreError};
+use crate::schema::{self, SCHEMA_VERSION};
+use crate::state_file::{self, Loaded, Lock};
+use crate::stats::Record;
+use crate::store::Store;
+
+/// The JSON layout of a state file. Games played without a name stay at the top, as in older files.
+#[derive(Default, Serialize, Deserialize)]
+pub struct GameState {
+    #[serde(default)]
+    schema_version: u64,
+    #[serde(flatten)]
+    unnamed: Profile,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    players: BTreeMap<String, Profile>,
+}
+
+impl GameState {
+    /// Parses the state file `path` with `content`, upgraded to the current schema; also says what the upgrade changed.
+    /// Every game must be one that can have been played, see `Record::problem`.
+    pub fn parse(path: &Path, content: &str) -> Result<(Self, Vec<String>), StateError> {
+        let (state, changes) = Self::upgrade(path, content)?;
+        for (player, games) in state.profiles() {
+            let of: String = of_player(player);
+            if let Some((i, problem)) = games.iter().enumerate().find_map(|(i, game)| Some((i, game.problem()?))) {
+                return Err(StateError::ImpossibleGame { path: path.to_path_buf(), game: format!("game {}{}", i + 1, of), problem });
+            }
+        }
+        Ok((state, changes))
+    }
+
+    // the state of `content` at the current schema, its games unchecked
+    fn upgrade(path: &Path, content: &str) -> Result<(Self, Vec<String>), StateError> {
+        let mut state: serde_json::Value = serde_json::from_str(content).map_err(|error| StateError::InvalidJson {
+            path: path.to_path_buf(),
+            line: error.line(),
+            column: error.column(),
+            message: error.to_string().trim_end_matches(&format!(" at line {} column {}", error.line(), error.column())).to_string(),
+        })?;
+        let changes: Vec<String> = schema::migrate(path, &mut state)?;
+        let state: GameState = serde_json::from_value(state).map_err(|error| StateError::InvalidLayout { path: path.to_path_buf(), message: error.to_string() })?;
+        Ok((state, changes))
+    }
+
+    /// Reads and parses the state file `path`.
+    pub fn read(path: &Path) -> Result<(Self, Vec<String>), StateError> {
+        let content: String = fs::read_to_string(path).map_err(|source| StateError::Unreadable { path: path.to_path_buf(), source })?;
+        Self::parse(path, &content)
+    }
+
+    // every profile, the games played without a name first
+    fn profiles(&self) -> impl Iterator<Item = (Option<&str>, &[Record])> {
+        let unnamed = std::iter::once((None, self.unnamed.games.as_deref().unwrap_or_default()));
+        unnamed.chain(self.players.iter().map(|(player, profile)| (Some(player.as_str()), profile.games.as_deref().unwrap_or_default())))
+    }
+
+    // the games of `player`, or those played without a name
+    fn games(&self, player: Option<&str>) -> Option<&Vec<Record>> {
+        match player {
+            Some(player) => self.players.get(player)?.games.as_ref(),
+            None => self.unnamed.games.as_ref(),
+        }
+    }
+
+    fn profile_mut(&mut self, player: Option<&str>) -> &mut Profile {
+        match player {
+            Some(player) => self.players.entry(player.to_string()).or_default(),
+            None => &mut self.unnamed,
+        }
+    }
+}
+
+/// The games of one player, kept in the state file under their name.
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct Profile {
+    total_rounds: Option<u32>,
+    games: Option<Vec<Record>>,
+}
+
+/// The history in one JSON state file, replaced as a whole on every save.
+pub struct JsonStore {
+    path: PathBuf,
+    state: GameState, // as last read or written
+    recovered: bool, // read from the backup
+}
+
+impl JsonStore {
+    /// A missing file starts empty and a damaged one is read from its backup; a file that cannot be read
+    /// or has a newer schema is an error, as saving would replace it.
+    pub fn open(path: &Path) -> Result<Self, StateError> {
+        let (state, recovered) = match state_file::read(path, GameState::read, StateError::is_damage) {
+            Loaded::Missing => (GameState::default(), false),
+            Loaded::Primary((state, _)) => (state, false),
+            Loaded::Backup((state, _)) => (state, true),
+            Loaded::Invalid(error) => return Err(error),
+        };
+        Ok(JsonStore { path: path.to_path_buf(), state, recovered })
+    }
+}
+
+impl Store for JsonStore {
+    fn games(&self, player: Option<&str>) -> Result<Vec<Record>, StoreError> {
+        Ok(self.state.games(player).cloned().unwrap_or_default())
+    }
+
+    fn players(&self) -> Result<Vec<String>, StoreError> {
+        Ok(self.state.players.keys().cloned().collect())
+    }
+
+    fn append(&mut self, player: Option<&str>, games: &[Record]) -> Result<(), StoreError> {
+        self.update(player, |stored| stored.extend_from_slice(games))
+    }
+
+    fn replace(&mut self, player: Option<&str>, games: &[Record]) -> Result<(), StoreError> {
+        self.update(player, |stored| *stored = games.to_vec())
+    }
+
+    fn is_recovered(&self) -> bool {
+        self.recovered
+    }
+}
+
+impl JsonStore {
+    // changes the games of `player` in the file as it is now and writes it back
+    fn update(&mut self, player: Option<&str>, change: impl FnOnce(&mut Vec<Record>)) -> Result<(), StoreError> {
+        let unwritable = |source: io::Error| StateError::Unwritable { path: self.path.clone(), source };
+        let _lock: Lock = state_file::lock(&self.path).map_err(unwritable)?;
+        // another session may have saved since, so add to what is there now; a damaged file is not worth
+        // a backup, it would replace a good one, and is kept aside rather than written over
+        let (mut state, intact) = match state_file::read(&self.path, GameState::read, StateError::is_damage) {
+            Loaded::Primary((state, _)) => (state, true),
+            Loaded::Backup((state, _)) => {
+                state_file::set_aside(&self.path).map_err(unwritable)?;
+                (state, false)
+            }
+            Loaded::Missing => (mem::take(&mut self.state), false),
+            Loaded::Invalid(error) => return Err(error.into()),
+        };
+        let profile: &mut Profile = state.profile_mut(player);
+        let stored: &mut Vec<Record> = profile.games.get_or_insert_with(Vec::new);
+        change(stored);
+        profile.total_rounds = Some(stored.len() as u32);
+        state.schema_version = SCHEMA_VERSION;
+        state_file::write(&self.path, &state, intact).map_err(unwritable)?;
+        self.state = state;
+        Ok(())
+    }
+}
+
+/// What checking a state file found.
+pub struct StateReport {
+    pub version: u64, // the schema version of the file as it was
+    pub games: usize, // of all players
+    pub changes: Vec<String>, // made by bringing the file up to date
+    pub problems: Vec<String>, // games that cannot have been played
+}
+
+/// Reads the state file `path`, brings it up to the current schema and checks every game.
+/// With `migrate` the upgraded file replaces the old one, which is kept as the backup.
+pub fn check_state_file(path: &Path, migrate: bool) -> Result<StateReport, StateError> {
+    let _lock: Option<Lock> = if migrate {
+        Some(state_file::lock(path).map_err(|source| StateError::Unwritable { path: path.to_path_buf(), source })?)
+    } else {
+        None
+    };
+    let content: String = fs::read_to_string(path).map_err(|source| StateError::Unreadable { path: path.to_path_buf(), source })?;
+    let version: u64 = serde_json::from_str(&content).ok().map_or(Ok(0), |state| schema::version(path, &state))?;
+    // every problem is reported, not just the first
+    let (state, changes) = GameState::upgrade(path, &content)?;
+
+    let mut report = StateReport { version, games: 0, changes, problems: Vec::new() };
+    for (player, games) in state.profiles() {
+        let of: String = of_player(player);
+        report.games += games.len();
+        let problems = games.iter().enumerate().filter_map(|(i, game)| Some(format!("game {}{}: {}", i + 1, of, game.problem()?)));
+        report.problems.extend(problems);
+    }
+    if migrate && version < SCHEMA_VERSION {
+        state_file::write(path, &state, true).map_err(|source| StateError::Unwritable { path: path.to_path_buf(), source })?;
+    }
+    Ok(report)
+}
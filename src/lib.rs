@@ -0,0 +1,41 @@
+//! The wordle engine behind the `wordle` binary, free of any terminal I/O.
+//!
+//! ```
+//! use wordle::game::{Game, GuessOutcome, LetterStatus};
+//! use wordle::word_list;
+//!
+//! let acceptable: Vec<String> = word_list::builtin_acceptable(5);
+//! let mut game = Game::new("CRANE".to_string(), false, 6, acceptable.clone());
+//!
+//! match game.guess("trace", &acceptable) {
+//!     GuessOutcome::Continue(status) => {
+//!         assert_eq!(status[0], LetterStatus::Red);
+//!         assert_eq!(status[2], LetterStatus::Green);
+//!     }
+//!     outcome => panic!("unexpected {:?}", outcome),
+//! }
+//! assert!(matches!(game.guess("xxxxx", &acceptable), GuessOutcome::Invalid(_)));
+//! assert!(matches!(game.guess("crane", &acceptable), GuessOutcome::Won));
+//! ```
+
+pub mod analysis;
+pub mod assist;
+pub mod builtin_words;
+pub mod constraints;
+pub mod daily;
+pub mod error;
+pub mod export;
+pub mod frontend;
+pub mod game;
+pub mod json_store;
+pub mod merge;
+pub mod pattern;
+pub mod reverse;
+pub mod schema;
+pub mod share;
+pub mod solver;
+pub mod sqlite_store;
+pub mod state_file;
+pub mod stats;
+pub mod store;
+pub mod word_list;
@@ -1,8 +1,9 @@
-use core::panic;
 use std::{
-    env, io::{self, Write}, process::{self}
+    fs, io::{self, Write}, path::{Path, PathBuf}, process, time::Duration,
 };
-use text_io::read;
+use anyhow::{anyhow, bail, Context};
+use clap::Parser;
+use colored::Colorize;
 use crossterm::{
     event::{DisableMouseCapture, EnableMouseCapture},
     execute,
@@ -12,452 +13,611 @@ use tui::{
     backend::CrosstermBackend,
     Terminal,
 };
-use console;
 use rayon::prelude::*;
+use fltk::app;
 
-mod game;
-mod builtin_words;
 mod args;
-mod stats;
+mod cli;
 mod tui_mode;
 mod gui;
 
-use game::{Game, GuessWordStatus};
-use stats::Stats;
-use tui_mode::App;
-
-use fltk::{app, button::Button, prelude::*, window::*};
-use fltk::enums::FrameType;
-use fltk::{enums::Color, input::Input};
-use std::cell::RefCell;
-use std::rc::Rc;
-use std::process::exit;
-use chrono::Local;
-use std::thread;
-use fltk::frame::Frame;
-use chrono::Timelike;
-use fltk::button::CheckButton;
-
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-
-    let args: Vec<String> = env::args().collect();
-
-    let mut word_processor: args::WordProcessor = args::WordProcessor::new();
-    word_processor.process_args(&args);
-
-    let random_mode: bool = word_processor.random_mode;
-    let meet_word_argument: bool = word_processor.meet_word_argument;
-    let mut day: usize = word_processor.day_argument.unwrap();
-    let seed: u64 = word_processor.seed_argument.unwrap();
-    let difficult_mode: bool = word_processor.difficult_mode;
-    let word_argument: String = word_processor.word_argument.into_iter().collect();
-    let stats_mode: bool = word_processor.stats_mode;
-    let answer_list: Vec<String> = word_processor.final_set;
-    let acceptable_list: Vec<String> = word_processor.acceptable_set;
-    let state: Option<std::path::PathBuf> = word_processor.state_path;
-    let state_mode: bool = word_processor.state_mode;
-    let tui_mode: bool = word_processor.tui_mode;
-    let gui_mode: bool = word_processor.gui_mode;
-    let hint_mode: bool = word_processor.hint_mode;
-    let test_mode: bool = word_processor.test_mode;
-
-    let mut stats: Stats = if let Some(stats) = Stats::load(&state) {
-        stats
+use args::{AnalyseArgs, AssistArgs, Cli, Command, ExportArgs, HistoryArgs, MergeArgs, ReverseArgs, ShareArgs, StateCommand, StatsCommand, StoreArgs, WordProcessor};
+use cli::CliFrontend;
+use wordle::analysis::{self, GuessAnalysis};
+use wordle::assist::Assistant;
+use wordle::export::{self, Format};
+use wordle::frontend::{self, InputSource, Renderer};
+use wordle::game::{Game, GuessWordStatus};
+use wordle::merge::{self, MergeReport};
+use wordle::pattern::PatternMatrix;
+use wordle::reverse::{self, Deduction, Sequence};
+use wordle::share;
+use wordle::solver::Strategy;
+use wordle::json_store::{self, StateReport};
+use wordle::state_file;
+use wordle::schema::SCHEMA_VERSION;
+use wordle::stats::{Record, Setup, Stats, WordLists};
+use wordle::store::{self, Backend, Store};
+use tui_mode::Tui;
+
+fn main() {
+    if let Err(error) = run() {
+        report(error);
+    }
+}
+
+fn run() -> anyhow::Result<()> {
+    let cli: Cli = Cli::parse();
+
+    match cli.command {
+        None => play(WordProcessor::from_play_args(&cli.play)?),
+        Some(Command::Play(play_args)) => play(WordProcessor::from_play_args(&play_args)?),
+        Some(Command::Assist(assist_args)) => assist(&assist_args),
+        Some(Command::Solve(solve_args)) => {
+            let word_processor: WordProcessor = WordProcessor::from_common_args(&solve_args.common)?;
+            solve(word_processor, &solve_args)
+        }
+        Some(Command::Bench(bench_args)) => {
+            let word_processor: WordProcessor = WordProcessor::from_common_args(&bench_args.common)?;
+            bench(word_processor, &bench_args)
+        }
+        Some(Command::Stats(stats_args)) => match &stats_args.command {
+            Some(StatsCommand::Export(export_args)) => export(export_args),
+            Some(StatsCommand::Merge(merge_args)) => merge(merge_args),
+            None => show_stats(&stats_args),
+        },
+        Some(Command::Replay(replay_args)) => replay(&replay_args),
+        Some(Command::Analyse(analyse_args)) => analyse(&analyse_args),
+        Some(Command::History(history_args)) => history(&history_args),
+        Some(Command::Reverse(reverse_args)) => reverse(&reverse_args),
+        Some(Command::State(state_command)) => manage_state(&state_command),
+    }
+}
+
+// usage errors keep clap's format, everything else is INVALID in test mode
+fn report(error: anyhow::Error) -> ! {
+    if let Some(clap_error) = error.downcast_ref::<clap::Error>() {
+        clap_error.exit();
+    }
+    if atty::is(atty::Stream::Stdout) {
+        eprintln!("{} {:#}", "error:".red().bold(), error);
     } else {
-        panic!("Failed to load stats in json");
-    };
+        println!("INVALID");
+    }
+    process::exit(1);
+}
 
-    if !test_mode{
-        if gui_mode {
-            let app = app::App::default();
-            let wind: Rc<RefCell<DoubleWindow>> = Rc::new(RefCell::new(DoubleWindow::new(0, 0, 800, 800, "Home Page")));
-            wind.borrow_mut().set_pos(400, 0);
-            wind.borrow_mut().set_color(Color::White);
-            let input = Input::new(180, 200, 120, 30, "Name");
-            
-            let mut button = Button::new(180, 260, 120, 30, "Start");
-            button.set_color(Color::rgb_color(156, 34, 24));
-            button.set_frame(FrameType::FlatBox);
-            button.set_label_size(20);
-            button.set_label_color(Color::White);
-
-            let mut exit_button = Button::new(180, 320, 120, 30, "exit");
-            exit_button.set_color(Color::rgb_color(106, 170, 100));
-            exit_button.set_frame(FrameType::FlatBox);
-            exit_button.set_label_size(20);
-            exit_button.set_label_color(Color::White);
-
-            let mut frame = Frame::new(180, 750, 400, 30, "");
-            frame.set_frame(FrameType::FlatBox);
-            frame.set_color(Color::White);
-            frame.set_label_size(24);
-            frame.set_label_color(Color::Black);
-            
-            let mut frame_time = Frame::new(180, 400, 160, 40, "");
-            frame_time.set_frame(FrameType::FlatBox);
-            frame_time.set_color(Color::White);
-            frame_time.set_label_size(24);
-            frame_time.set_label_color(Color::Black);
-            
-            let mut difficult_check = CheckButton::new(400, 280, 40, 30, "Difficult Mode");
-            difficult_check.set_frame(FrameType::FlatBox);
-            difficult_check.set_label_size(16);
-            difficult_check.set_label_color(Color::Black);
-            difficult_check.set_color(Color::White);
-
-            let difficult_mode: Rc<RefCell<bool>> = Rc::new(RefCell::new(false)); 
-
-            let difficult_mode_clone = Rc::clone(&difficult_mode);
-            difficult_check.set_callback(move |b| {
-                let mut difficult_mode = difficult_mode_clone.borrow_mut();
-                *difficult_mode = b.is_checked().clone();
-            });
-
-            thread::spawn(move || {
-                let current_time = Local::now().format("%Y-%m-%d %H:%M:%S").to_string();   
-                let current_hour = Local::now().hour();   
-                if (0..8).contains(&current_hour) || (23..25).contains(&current_hour) {
-                    frame.set_label("Are you having trouble falling asleep? :/");
-                } else if (7 .. 9).contains(&current_hour) {
-                    frame.set_label("You should have breakfast first :P");
-                } else if (9 .. 13).contains(&current_hour) {
-                    frame.set_label("It's time to work :)");
-                } else if (13 .. 14).contains(&current_hour) {
-                    frame.set_label("You should have lunch first :P");
-                } else if (14 .. 18).contains(&current_hour) {
-                    frame.set_label("It's time to work :)");
-                } else if (18 .. 20).contains(&current_hour) {
-                    frame.set_label("You should have dinner first :p");
-                } else {
-                    frame.set_label("Take a break and go to bed early -_-zZ");
-                }
-                frame_time.set_label(&current_time);
-            });
-            let wind_clone = Rc::clone(&wind);
-            button.set_callback(move |_| {
-                let mut wind_clone = wind_clone.borrow_mut();
-                wind_clone.hide();
-            });
-            exit_button.set_callback(move |_| {
-                exit(0);
-            });
-
-            wind.borrow_mut().end();
-            wind.borrow_mut().show();
-            app.run().unwrap();
-                
-            let name: Rc<RefCell<Input>> = Rc::from(RefCell::from(input));
-            let name: String = name.borrow().value();
-
-            loop {
-                let res: bool = gui::run_gui(name.clone(), answer_list.clone(), seed.clone(), day.clone(), difficult_mode.borrow().clone(), acceptable_list.clone());
-                if !res {
-                    break;
-                }
-                day = day + 1;            
-            }
+fn load_stats(state: &Option<PathBuf>, backend: Option<Backend>, player: Option<&str>) -> anyhow::Result<Stats> {
+    let store: Option<Box<dyn Store>> = state.as_deref().map(|path| store::open(path, backend)).transpose()?;
+    let stats: Stats = Stats::load(store, player)?;
+    if stats.is_recovered() {
+        let path: &Path = state.as_deref().unwrap();
+        eprintln!("{} {}: damaged, loaded {} instead", "warning:".yellow().bold(), path.display(), state_file::backup_path(path).display());
+    }
+    Ok(stats)
+}
+
+// the history a subcommand reads, of the profile named like play names it
+fn load_stored(stored: &StoreArgs) -> anyhow::Result<Stats> {
+    load_stats(&Some(stored.state.clone()), stored.store, stored.player().as_deref())
+}
+
+// the history of the player, who may play a daily puzzle only once
+fn open_stats(word_processor: &WordProcessor) -> anyhow::Result<Stats> {
+    let stats: Stats = load_stats(&word_processor.state_path, word_processor.store, word_processor.player.as_deref())?;
+    if let Some(daily) = &word_processor.daily {
+        if stats.played_on(daily.date)? {
+            bail!("{} of {} has been played already, come back tomorrow", daily, daily.date);
         }
-        else if tui_mode {
-            enable_raw_mode()?;
-            let mut stdout = io::stdout();
-            execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
-        
-            let backend: CrosstermBackend<io::Stdout> = CrosstermBackend::new(stdout);
-            let mut terminal: Terminal<CrosstermBackend<io::Stdout>> = Terminal::new(backend)?;
-
-            let mut secret_word: String = String::new();
-
-            let mut temp_app =  App::default(secret_word, difficult_mode);
-            if !random_mode {
-                if meet_word_argument {
-                    secret_word = word_argument.to_uppercase();
-                } else {
-                    if let Ok(Some(word)) = App::input_answer(&mut terminal, &mut temp_app, &answer_list.clone()) {
-                        secret_word = word;
-                    } else {
-                        panic!("Unexpected error");
-                    }
-                }
-            } else {
-                secret_word = Game::get_seed_random_word(&answer_list, seed, day);
-            }
-            let mut _game: Game = Game::new(secret_word.to_string(), difficult_mode, acceptable_list.clone(), acceptable_list.clone());
-    
-            // initialize
-            let mut _app: App = App::default(secret_word, difficult_mode);
-            
-            // Muti game
-            loop {
-                // a round of game
-                loop {
-                    // one single guess
-                    let mut _valid_word: Option<String> = None;
-                    if let Ok(Some(word)) = App::run_app(&mut terminal, &mut _app, &acceptable_list, &mut _game, 0, false, false) {
-                        _valid_word = Some(word.to_uppercase());
-                    } else { 
-                        panic!("Unexpected errors");
-                    }
-                    // game over
-                    if let Some(word) = _valid_word {
-                        // if game over
-                        if _game.is_game_over(&word, false, false) {
-                            stats.update(&_game.guesses, _game.answer.to_string(), _game.is_win);
-                            // save game status in json
-                            if state_mode {
-                                stats.save();
-                            }        
-                            let frequent_words: Vec<(&String, &usize)> = stats.get_frequent_words();
-                            let mut words: Vec<(String, usize)> = Vec::new();
-                            for (_, (word, count)) in frequent_words.iter().take(5).enumerate() {
-                                words.push((word.to_string(), **count));
-                            }
-                
-                            _app.load_stats_message(stats.get_wins(), stats.get_fails(), stats.get_success_rate(), words);
-                            if _game.is_win {
-                                // if win
-                                if let Ok(Some(_word)) = App::run_app(&mut terminal, &mut _app, &acceptable_list, &mut _game, 1, false, false) {
-                                    // show statistic
-                                    let _ = App::run_app(&mut terminal, &mut _app, &acceptable_list, &mut _game, 0, false, true);
-                                    break;
-                                } else {
-                                    panic!("Unexpected errors");
-                                }    
-                            } else {
-                                // if lose
-                                if let Ok(Some(_word)) = App::run_app(&mut terminal, &mut _app, &acceptable_list, &mut _game, 2, false, false) {
-                                    // show statistic
-                                    let _ = App::run_app(&mut terminal, &mut _app, &acceptable_list, &mut _game, 0, false, true);
-                                    break;
-                                } else {
-                                    panic!("Unexpected errors");
-                                }
-                            }
-                        } else {
-                            // keep guessing 
-                            continue;
-                        }
-                    } else {
-                        panic!("Unexpected errors");
-                    }
-                }
-                // start next round
-                if let Ok(Some(_word)) = App::run_app(&mut terminal, &mut _app, &acceptable_list, &mut _game, 0, true, false) {
-                    if !random_mode && meet_word_argument {
-                        break;
-                    }
-                    if _app.continue_to_play == true {
-                        // continue to play
-                        if !random_mode {
-                            let _temp_secret_answer: String = String::new();
-                            let mut temp_app = App::default(_temp_secret_answer, difficult_mode);
-                            if let Ok(Some(word)) = App::input_answer(&mut terminal, &mut temp_app, &answer_list.clone()) {
-                                secret_word = word;
-                            } else {
-                                panic!("Unexpected error");
-                            }
-                        } else {
-                            day += 1;
-                            secret_word = Game::get_seed_random_word(&answer_list, seed, day);
-                        }
-                        _game = Game::new(secret_word.clone(), difficult_mode, acceptable_list.clone(), acceptable_list.clone());
-                        _app = App::default(secret_word, difficult_mode);
-                        terminal.draw(|f|App::ui(f, &mut _app))?;
-                        continue;
-
-                    } else {
-                        // exit the game
-                        break;
-                    }
+    }
+    Ok(stats)
+}
 
-                } else {
-                    panic!("Unexpected errors");
-                }
+fn play(mut word_processor: WordProcessor) -> anyhow::Result<()> {
+    if word_processor.gui_mode {
+        let app: app::App = app::App::default();
+        let (name, difficult_mode) = match gui::home_page(&app) {
+            Some(choice) => choice,
+            None => return Ok(()),
+        };
+        word_processor.difficult_mode = difficult_mode;
+        if word_processor.player.is_none() {
+            word_processor.player = args::player_name(Some(name.clone()));
+        }
+        let mut stats: Stats = open_stats(&word_processor)?;
+        run_session(&mut gui::Gui::new(app, &name), &word_processor, &mut stats, false)?;
+    } else if word_processor.tui_mode {
+        let mut stats: Stats = open_stats(&word_processor)?;
+        with_tui(word_processor.max_attempts, |tui| run_session(tui, &word_processor, &mut stats, false))?;
+    } else {
+        // automatically determine if it is in interactive mode
+        let is_tty: bool = atty::is(atty::Stream::Stdout);
+        let mut frontend: CliFrontend = CliFrontend::new(is_tty, word_processor.stats_mode);
+        frontend.greet(&mut word_processor.player);
+        let mut stats: Stats = open_stats(&word_processor)?;
+        // hints are only readable on a terminal
+        let hint_mode: bool = word_processor.hint_mode && is_tty;
+        run_session(&mut frontend, &word_processor, &mut stats, hint_mode)?;
+    }
+
+    Ok(())
+}
+
+// runs `session` on the full screen terminal front end, restoring the terminal afterwards
+fn with_tui(max_attempts: usize, session: impl FnOnce(&mut Tui<CrosstermBackend<io::Stdout>>) -> anyhow::Result<()>) -> anyhow::Result<()> {
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+
+    let backend: CrosstermBackend<io::Stdout> = CrosstermBackend::new(stdout);
+    let terminal: Terminal<CrosstermBackend<io::Stdout>> = Terminal::new(backend)?;
+    let mut tui: Tui<CrosstermBackend<io::Stdout>> = Tui::new(terminal, max_attempts);
+    let result: anyhow::Result<()> = session(&mut tui);
+
+    // restore terminal
+    let mut terminal: Terminal<CrosstermBackend<io::Stdout>> = tui.into_terminal();
+    disable_raw_mode()?;
+    execute!(
+        terminal.backend_mut(),
+        LeaveAlternateScreen,
+        DisableMouseCapture
+    )?;
+    terminal.show_cursor()?;
+    result
+}
+
+// games one after another on any front end until the player stops, recording each finished one
+fn run_session<F: InputSource + Renderer>(frontend: &mut F, word_processor: &WordProcessor, stats: &mut Stats, hint_mode: bool) -> anyhow::Result<()> {
+    let answer_list: &Vec<String> = &word_processor.final_set;
+    let acceptable_list: &Vec<String> = &word_processor.acceptable_set;
+    let mut day: usize = word_processor.day_argument;
+    let word_lists: WordLists = WordLists::new(word_processor.final_set_file.as_deref(), answer_list, word_processor.acceptable_set_file.as_deref(), acceptable_list);
+
+    loop {
+        let answer: String = if let Some(daily) = &word_processor.daily {
+            daily.answer(answer_list, word_processor.seed_argument)
+        } else if word_processor.random_mode {
+            Game::get_seed_random_word(answer_list, word_processor.seed_argument, day)
+        } else if let Some(word) = &word_processor.word_argument {
+            word.to_uppercase()
+        } else {
+            match frontend::read_answer(frontend, answer_list, word_processor.word_length) {
+                Some(answer) => answer,
+                None => return Ok(()),
             }
+        };
+
+        let mut game: Game = Game::new(answer, word_processor.difficult_mode, word_processor.max_attempts, acceptable_list.clone());
+        game.strategy = word_processor.strategy;
+        game.strict = word_processor.strict_mode;
+        game.daily = word_processor.daily;
+        if (hint_mode || word_processor.analysis_mode) && word_processor.probes {
+            game.probe_list = acceptable_list.clone();
+        }
+        if !frontend::play_round(frontend, &mut game, acceptable_list, hint_mode) {
+            return Ok(());
+        }
+        if let Some(share_args) = &word_processor.share {
+            share_result(frontend, &share::share_game(&game, share_args.palette()), share_args)?;
+        }
+        if word_processor.analysis_mode {
+            frontend.render_analysis(&analysis::analyse(&game));
+        }
+
+        // update the game status so far
+        let seeded: bool = word_processor.random_mode || word_processor.daily.is_some();
+        let setup: Setup = Setup {
+            random: word_processor.random_mode,
+            seed: seeded.then_some(word_processor.seed_argument),
+            day: word_processor.random_mode.then_some(day),
+            word_lists: Some(word_lists.clone()),
+        };
+        stats.update(&game, &setup);
+        frontend.render_stats(stats);
+        if word_processor.state_mode {
+            stats.save().with_context(|| format!("{}: failed to save the state", word_processor.state_path.as_ref().unwrap().display()))?;
+        }
+
+        // only a single game when the answer is given on the command line or by the date
+        if word_processor.meet_word_argument || word_processor.daily.is_some() || !frontend.ask_continue() {
+            return Ok(());
+        }
+        day += 1;
+    }
+}
 
-            // restore terminal
-            disable_raw_mode()?;
-            execute!(
-                terminal.backend_mut(),
-                LeaveAlternateScreen,
-                DisableMouseCapture
-            )?;
-            terminal.show_cursor()?;
+// show a result block, or append it to the share file
+fn share_result<F: Renderer + ?Sized>(frontend: &mut F, text: &str, share_args: &ShareArgs) -> anyhow::Result<()> {
+    match &share_args.share_file {
+        Some(path) => append_share(path, text),
+        None => {
+            frontend.render_share(text);
+            Ok(())
         }
+    }
+}
 
-        else {
-            // automatically determine if it is in interactive mode
-            let is_tty = atty::is(atty::Stream::Stdout);
-            if is_tty {
-                print!("{}", console::style("Your name: ").bold().red());
-                io::stdout().flush().unwrap();
-                let mut line = String::new();
-                io::stdin().read_line(&mut line)?;
-                println!("Welcome to wordle, {}!", line.trim());
+// blocks are separated by an empty line
+fn append_share(path: &Path, text: &str) -> anyhow::Result<()> {
+    let mut file: fs::File = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("{}: failed to open the share file", path.display()))?;
+    writeln!(file, "{}\n", text).with_context(|| format!("{}: failed to write the share file", path.display()))
+}
+
+// a stored game turn by turn
+fn replay(replay_args: &args::ReplayArgs) -> anyhow::Result<()> {
+    let stats: Stats = load_stored(&replay_args.stored)?;
+    let number: usize = replay_args.game.unwrap_or(stats.get_games_count());
+    let record: &Record = match number.checked_sub(1) {
+        Some(index) => stats.get_record(index)?,
+        None => None,
+    }
+    .ok_or_else(|| anyhow!("{}: there is no such game to replay", replay_args.stored.state.display()))?;
+    cli::print_replay(record, number, atty::is(atty::Stream::Stdout), Duration::from_millis(replay_args.delay));
+    Ok(())
+}
+
+// stored games guess by guess against the recommendations on the word lists given now
+fn analyse(analyse_args: &AnalyseArgs) -> anyhow::Result<()> {
+    let word_processor: WordProcessor = WordProcessor::from_common_args(&analyse_args.common)?;
+    let stats: Stats = load_stored(&analyse_args.stored)?;
+    let numbers: Vec<usize> = match analyse_args.game {
+        Some(number) => vec![number],
+        None => (1..=stats.get_games_count()).collect(),
+    };
+    let is_tty: bool = atty::is(atty::Stream::Stdout);
+    let mut guesses: Vec<GuessAnalysis> = Vec::new();
+    for number in numbers {
+        let record: &Record = match number.checked_sub(1) {
+            Some(index) => stats.get_record(index)?,
+            None => None,
+        }
+        .ok_or_else(|| anyhow!("{}: there is no such game to analyse", analyse_args.stored.state.display()))?;
+        // the word lists given now only fit games of their length
+        if record.answer.len() != word_processor.word_length {
+            let notice: String = format!("game {} has {} letters, the word lists {}", number, record.answer.len(), word_processor.word_length);
+            if analyse_args.game.is_some() {
+                bail!("{}: {}", analyse_args.stored.state.display(), notice);
             }
+            eprintln!("{} {}: {}, skipped", "warning:".yellow().bold(), analyse_args.stored.state.display(), notice);
+            continue;
+        }
+        let mut game: Game = Game::new(record.answer.clone(), record.difficult, record.max_attempts(), word_processor.acceptable_set.clone());
+        game.strategy = word_processor.strategy;
+        game.strict = record.strict;
+        if word_processor.probes {
+            game.probe_list = word_processor.acceptable_set.clone();
+        }
+        for guess in &record.guesses {
+            game.play(guess);
+        }
+        let analysis: Vec<GuessAnalysis> = analysis::analyse(&game);
+        if is_tty {
+            println!("{}", format!("Game {} {}", number, record.answer).bold());
+        } else {
+            println!("{} {}", number, record.answer);
+        }
+        cli::print_analysis(&analysis, is_tty);
+        guesses.extend(analysis);
+    }
+    cli::print_analysis_summary(&guesses, is_tty);
+    Ok(())
+}
 
-            let mut secret_word: String;
+// the stored games that match the filters, numbered as in `replay`
+fn history(history_args: &HistoryArgs) -> anyhow::Result<()> {
+    let store: Box<dyn Store> = store::open(&history_args.stored.state, history_args.stored.store)?;
+    let player: Option<String> = history_args.stored.player();
+    let games: Vec<(usize, Record)> = store.find(player.as_deref(), &history_args.filter())?;
+    cli::print_history(&games, atty::is(atty::Stream::Stdout));
+    Ok(())
+}
 
-            // word pattern
-            if random_mode == false {
-                if meet_word_argument {
-                    secret_word = word_argument.to_uppercase();
-                } else {
-                    if is_tty {
-                        println!("Please type in the answer to start the game: ");
-                    }
-                    secret_word = input_ans(&answer_list, is_tty); 
+// the statistics of a state file, its leaderboard, or the result block of one of its games
+fn show_stats(stats_args: &args::StatsArgs) -> anyhow::Result<()> {
+    let stored: &StoreArgs = stats_args.stored.as_ref().expect("required without a subcommand");
+    let state: &Path = &stored.state;
+    let stats: Stats = load_stored(stored)?;
+    if stats_args.leaderboard {
+        cli::print_leaderboard(&stats.get_leaderboard(), atty::is(atty::Stream::Stdout));
+        return Ok(());
+    }
+    let game: Option<usize> = match stats_args.share {
+        Some(game) => game.or(Some(stats.get_games_count())),
+        None => {
+            let is_tty: bool = atty::is(atty::Stream::Stdout);
+            cli::print_stats(&stats, is_tty);
+            // a terminal shows the panel anyway
+            if stats_args.detailed && !is_tty {
+                for line in cli::stats_panel(&stats) {
+                    println!("{}", line);
                 }
-            } else { // random pattern
-                secret_word = Game::get_seed_random_word(&answer_list, seed, day);
             }
+            return Ok(());
+        }
+    };
+    let text: String = match game.and_then(|game| game.checked_sub(1)) {
+        Some(index) => stats.share(index, stats_args.share_args.palette())?,
+        None => None,
+    }
+    .ok_or_else(|| anyhow!("{}: there is no such game to share", state.display()))?;
+    match &stats_args.share_args.share_file {
+        Some(path) => append_share(path, &text),
+        None => {
+            println!("{}", text);
+            Ok(())
+        }
+    }
+}
 
-            let mut game: Game = Game::new(secret_word.to_string(), difficult_mode, acceptable_list.clone(), acceptable_list.clone());
+// one row per stored game or per guess, for notebooks and spreadsheets
+fn export(export_args: &ExportArgs) -> anyhow::Result<()> {
+    let stats: Stats = load_stored(&export_args.stored)?;
+    let mut out: Box<dyn Write> = match &export_args.output {
+        Some(path) => Box::new(fs::File::create(path).with_context(|| format!("{}: failed to create the export file", path.display()))?),
+        None => Box::new(io::stdout().lock()),
+    };
+    let format: Format = export_args.format;
+    let written: io::Result<()> = if export_args.guesses {
+        export::write(&mut out, &export::guess_rows(stats.get_records()?), format)
+    } else {
+        export::write(&mut out, &export::game_rows(stats.get_records()?), format)
+    };
+    written.and_then(|()| out.flush()).context("failed to write the export")
+}
 
-            if is_tty {
-                println!("You have 6 chances to guess the word!");
-                println!();
-            }
+// the games of every source added to the output, and what came of it
+fn merge(merge_args: &MergeArgs) -> anyhow::Result<()> {
+    if let Some(missing) = merge_args.sources.iter().find(|source| !source.exists()) {
+        bail!("{}: no such state file", missing.display());
+    }
+    let report: MergeReport = merge::merge(&merge_args.sources, &merge_args.output)?;
+    println!("{}: {} games added, {} duplicates skipped", merge_args.output.display(), report.added, report.duplicates);
+    for (player, stats) in &report.profiles {
+        println!(
+            "{}: {} games, {} won, {} lost, streak {}, max streak {}, {} words",
+            player.as_deref().unwrap_or("(no name)"),
+            stats.get_games_count(),
+            stats.get_wins(),
+            stats.get_fails(),
+            stats.get_current_streak(),
+            stats.get_max_streak(),
+            stats.get_frequent_words().len()
+        );
+    }
+    for conflict in &report.conflicts {
+        println!("conflict {}", conflict);
+    }
+    Ok(())
+}
 
-            loop {
-                let attempts = game.get_tries() + 1;
-                if is_tty {
-                    println!("ROUND{}:", attempts);
-                    println!("Enter your guess: ");
-                }
+// check a state file, upgrading it with `migrate`; what changed is reported either way
+fn manage_state(state_command: &StateCommand) -> anyhow::Result<()> {
+    let (path, migrate): (&Path, bool) = match state_command {
+        StateCommand::Migrate(file_args) => (&file_args.state, true),
+        StateCommand::Validate(file_args) => (&file_args.state, false),
+    };
+    if Backend::of(path) == Backend::Sqlite {
+        bail!("{}: only JSON state files have a schema version", path.display());
+    }
+    let report: StateReport = json_store::check_state_file(path, migrate)?;
+    let upgrade: String = if report.version == SCHEMA_VERSION {
+        String::new()
+    } else if migrate {
+        format!(", upgraded to {}", SCHEMA_VERSION)
+    } else {
+        format!(", needs upgrading to {}", SCHEMA_VERSION)
+    };
+    println!("{}: schema version {}, {} games{}", path.display(), report.version, report.games, upgrade);
+    for change in &report.changes {
+        println!("change {}", change);
+    }
+    for problem in &report.problems {
+        println!("problem {}", problem);
+    }
+    if !report.problems.is_empty() {
+        bail!("{}: {} games cannot have been played", path.display(), report.problems.len());
+    }
+    Ok(())
+}
 
-                let word: String = match game.ask_for_guess(&acceptable_list) {
-                    Ok(word) => word,
-                    Err(error) => {
-                        println!("{}", error.print_error(is_tty));
-                        continue;
-                    }
-                };
-
-                let result: GuessWordStatus = game.play(&word);
-
-                if is_tty {
-                    game.print_guess_history();
-                    if hint_mode {
-                        if !game.is_game_over(&word, is_tty, false) {
-                            let current_hint_list = game.get_hint(&word, result);
-                            println!("Here are all possible words");
-                            for hint in current_hint_list.clone(){
-                                println!("{}", hint);
-                            }
-                            println!();
-                            println!("Do you need recommendations for the most likely words?");
-                            println!("<y> for yes, <n> for No");
-                            let request: char = read!();
-                            if request == 'y' || request == 'Y' {
-                                let recommend_list: Vec<String> = game.get_recommend_words(&current_hint_list);
-                                for (index, recommend_word) in recommend_list.iter().enumerate() {
-                                    println!("{}: {}", index + 1, recommend_word);
-                                }
-                            } else {
-
-                            }
-                            
-                            game.hint_list = current_hint_list;
-                        }
-                    }
-                } else {
-                    game.print_status_word(&word, &result);
-                    game.print_status_alphabet();
-                }
+// suggestions for a game played elsewhere, on the word lists and strategy given
+fn assist(assist_args: &AssistArgs) -> anyhow::Result<()> {
+    let word_processor: WordProcessor = WordProcessor::from_common_args(&assist_args.common)?;
+    let mut assistant: Assistant = Assistant::new(
+        word_processor.word_length,
+        assist_args.difficult,
+        word_processor.max_attempts,
+        word_processor.final_set.clone(),
+        word_processor.acceptable_set.clone(),
+    );
+    assistant.strategy = word_processor.strategy;
+    assistant.strict = assist_args.strict;
+    if word_processor.probes {
+        assistant.probe_list = word_processor.acceptable_set.clone();
+    }
+    if assist_args.tui {
+        with_tui(word_processor.max_attempts, |tui| {
+            frontend::assist(tui, &mut assistant);
+            Ok(())
+        })
+    } else {
+        frontend::assist(&mut CliFrontend::new(atty::is(atty::Stream::Stdout), false), &mut assistant);
+        Ok(())
+    }
+}
 
-                if game.is_game_over(& word, is_tty, true) {
-                    // update the game status so far
-                    stats.update(&game.guesses, game.answer.to_string(), game.is_win);
-                    if stats_mode {
-                        stats.print_stats(is_tty);
-                    }
-                    if state_mode {
-                        stats.save();
-                    }
-                    // continue in non specified answer mode
-                    if !meet_word_argument {
-                        if game.continue_to_play(is_tty) {
-                            if random_mode {
-                                loop {
-                                    day += 1;
-                                    secret_word = Game::get_seed_random_word(&answer_list, seed, day);
-                                    game = Game::new(secret_word, difficult_mode, acceptable_list.clone(), acceptable_list.clone());
-                                    break;
-                                }
-                            } else {
-                                if is_tty{
-                                    println!("Please type in the answer to start the game: ");
-                                }
-                                secret_word = input_ans(&acceptable_list, is_tty);
-                                game = Game::new(secret_word, difficult_mode, acceptable_list.clone(), acceptable_list.clone());
-                            }
-                        } else {
-                            process::exit(0);
-                        }
-                    }
-                    else {
-                        break;
-                    }
-                }
+// the answers a result block may hide and the guesses most likely behind it
+fn reverse(reverse_args: &ReverseArgs) -> anyhow::Result<()> {
+    let word_processor: WordProcessor = WordProcessor::from_common_args(&reverse_args.common)?;
+    let text: String = match &reverse_args.grid {
+        Some(path) => fs::read_to_string(path).with_context(|| format!("{}: cannot read the result block", path.display()))?,
+        None => io::read_to_string(io::stdin())?,
+    };
+    let rows: Vec<GuessWordStatus> = reverse::parse_grid(&text, word_processor.word_length, word_processor.max_attempts)?;
+    let answers: Vec<String> = match &reverse_args.answer {
+        Some(answer) => {
+            let answer: String = answer.trim().to_uppercase();
+            if answer.len() != word_processor.word_length || !answer.chars().all(|c| c.is_ascii_alphabetic()) {
+                bail!("{} is not a word of {} letters", answer, word_processor.word_length);
             }
+            vec![answer]
+        }
+        None => word_processor.final_set.clone(),
+    };
+
+    let deductions: Vec<Deduction> = reverse::deduce(&rows, &answers, &word_processor.acceptable_set);
+    let fitting: Vec<String> = deductions.iter().map(|deduction| deduction.answer.clone()).collect();
+    let sequences: Vec<Sequence> = reverse::sequences(&rows, &fitting, &word_processor.final_set, reverse_args.sequences);
+    let is_tty: bool = atty::is(atty::Stream::Stdout);
+    cli::print_deductions(&deductions, is_tty);
+    cli::print_sequences(&sequences, &rows, is_tty);
+    Ok(())
+}
+
+// let the recommender play against a known answer
+fn solve(word_processor: WordProcessor, solve_args: &args::SolveArgs) -> anyhow::Result<()> {
+    let is_tty: bool = atty::is(atty::Stream::Stdout);
+    let acceptable_list: Vec<String> = word_processor.acceptable_set;
+    let answer: String = solve_args.word.trim().to_uppercase();
+    if !acceptable_list.contains(&answer) {
+        bail!("{} is not in the acceptable word list", answer);
+    }
+
+    let mut game: Game = Game::new(answer, solve_args.difficult, word_processor.max_attempts, acceptable_list.clone());
+    game.strategy = word_processor.strategy;
+    if word_processor.probes {
+        game.probe_list = acceptable_list.clone();
+    }
+    let mut guess: String = match &solve_args.start {
+        Some(start) => start.trim().to_uppercase(),
+        None => game.get_recommend_words(&acceptable_list)[0].clone(),
+    };
+    if !acceptable_list.contains(&guess) {
+        bail!("{} is not in the acceptable word list", guess);
+    }
+
+    loop {
+        let result: GuessWordStatus = game.play(&guess);
+        if is_tty {
+            cli::print_colored_word(&guess, &result);
+            println!();
+        } else {
+            cli::print_status_word(&guess, &result);
+            println!("{}", guess);
         }
-    } 
-    // test mode: 
-    // caculate all attempts for each answer in default final list and each guess word in available list
-    else {
-        let mut attempt_sequence: Vec<Vec<u64>> = Vec::new();
-        for ans_word in answer_list {
-            let attempts_for_one_answer: Vec<u64> = acceptable_list.clone().par_iter().map(|guess_word| {
-                let mut game = Game::new(ans_word.clone(), false, acceptable_list.clone(), acceptable_list.clone());
-                game.over_all_game_numbers(guess_word)
-            }).collect();
-
-        attempt_sequence.push(attempts_for_one_answer);
+        if game.is_game_over(&guess) {
+            cli::print_game_over(&game, is_tty);
+            break;
         }
-        print_attempts_result(&attempt_sequence);
+        let candidates: Vec<String> = game.get_hint();
+        guess = game.get_recommend_words(&candidates)[0].clone();
     }
+    Ok(())
+}
 
+// caculate all attempts for each answer in the final list and each guess word in the acceptable list
+fn bench(word_processor: WordProcessor, bench_args: &args::BenchArgs) -> anyhow::Result<()> {
+    let acceptable_list: &Vec<String> = &word_processor.acceptable_set;
+    let matrix: Option<PatternMatrix> = match &bench_args.matrix {
+        Some(path) => PatternMatrix::load_or_build(path, acceptable_list, acceptable_list)
+            .with_context(|| format!("{}: failed to cache the pattern matrix", path.display()))?,
+        None => PatternMatrix::new(acceptable_list, acceptable_list),
+    };
+
+    let difficult: bool = bench_args.difficult;
+    if bench_args.compare.is_empty() {
+        let attempt_sequence: Vec<Vec<u64>> = bench_attempts(&word_processor, word_processor.strategy, difficult, &matrix);
+        print_attempts_result(&attempt_sequence)?;
+        print_bench_summary(word_processor.strategy, difficult, &attempt_sequence, word_processor.max_attempts);
+    } else {
+        for &strategy in &bench_args.compare {
+            let attempt_sequence: Vec<Vec<u64>> = bench_attempts(&word_processor, strategy, difficult, &matrix);
+            print_bench_summary(strategy, difficult, &attempt_sequence, word_processor.max_attempts);
+        }
+    }
     Ok(())
 }
 
-fn input_ans(word_list:&Vec<String>, is_tty: bool) -> String {
-    loop {
-        match Game::get_secret_word(&word_list) {
-            Ok(word) => {
-                return word;
+// attempts indexed as [answer][first guess]
+fn bench_attempts(word_processor: &WordProcessor, strategy: Strategy, difficult: bool, matrix: &Option<PatternMatrix>) -> Vec<Vec<u64>> {
+    let answer_list: &Vec<String> = &word_processor.final_set;
+    let acceptable_list: &Vec<String> = &word_processor.acceptable_set;
+
+    let mut attempt_sequence: Vec<Vec<u64>> = vec![Vec::with_capacity(acceptable_list.len()); answer_list.len()];
+    match matrix {
+        // integer lookups on the decision tree of each first guess
+        Some(matrix) => {
+            let answers: Vec<usize> = answer_list.iter().map(|word| acceptable_list.binary_search(word).unwrap()).collect();
+            let starts: Vec<usize> = (0..acceptable_list.len()).collect();
+            for chunk in starts.chunks(256) {
+                let counts: Vec<Vec<u64>> = chunk
+                    .par_iter()
+                    .map(|&start| strategy.count_guesses(matrix, word_processor.probes, difficult, start, &answers))
+                    .collect();
+                for start_counts in counts {
+                    for (slot, count) in start_counts.into_iter().enumerate() {
+                        attempt_sequence[slot].push(count);
+                    }
+                }
             }
-            Err(error) => {
-                println!("{}", error.print_error(is_tty));
-                continue;
+        }
+        // words too long for patterns are played out one game at a time
+        None => {
+            let probe_list: Vec<String> = if word_processor.probes { acceptable_list.clone() } else { Vec::new() };
+            for (slot, ans_word) in answer_list.iter().enumerate() {
+                attempt_sequence[slot] = acceptable_list.par_iter().map(|guess_word| {
+                    let mut game = Game::new(ans_word.clone(), difficult, word_processor.max_attempts, acceptable_list.clone());
+                    game.strategy = strategy;
+                    game.probe_list = probe_list.clone();
+                    game.over_all_game_numbers(guess_word)
+                }).collect();
             }
-        } 
+        }
     }
+    attempt_sequence
 }
 
-// test mode printer
-fn print_attempts_result(attemp_sequence: &Vec<Vec<u64>>) {
+// games that would have run out of attempts count as failures
+fn print_bench_summary(strategy: Strategy, difficult: bool, attempt_sequence: &[Vec<u64>], max_attempts: usize) {
+    let attempts = attempt_sequence.iter().flatten();
+    let games: usize = attempt_sequence.iter().map(|inner_vec| inner_vec.len()).sum();
+    let average: f64 = attempts.clone().sum::<u64>() as f64 / games.max(1) as f64;
+    let worst: u64 = attempts.clone().copied().max().unwrap_or(0);
+    let failures: usize = attempts.filter(|&&count| count > max_attempts as u64).count();
+    let mode: &str = if difficult { " (difficult)" } else { "" };
+    println!("{}{}: average {:.4}, worst case {}, failures {}", strategy, mode, average, worst, failures);
+}
+
+// bench printer
+fn print_attempts_result(attemp_sequence: &Vec<Vec<u64>>) -> io::Result<()> {
     let summed_attempt_sequence: Vec<u64> = attemp_sequence
         .par_iter() // par_iter()
         .map(|inner_vec| inner_vec.par_iter().sum())
         .collect();
 
-
+    // millions of numbers, so lock stdout once
+    let mut out = io::BufWriter::new(io::stdout().lock());
     for (i, inner_vec) in attemp_sequence.iter().enumerate() {
-        println!("\n--- word: {} ---", i + 1);
+        writeln!(out, "\n--- word: {} ---", i + 1)?;
         for (index, element) in inner_vec.iter().enumerate() {
             if index % 80 == 0 && index != 0 {
-                println!();
+                writeln!(out)?;
             }
-            print!("{} ", element);
+            write!(out, "{} ", element)?;
         }
-        println!();
+        writeln!(out)?;
         let average: f64 = summed_attempt_sequence[i] as f64/ attemp_sequence[0].len() as f64;
-        println!("average attempts: {}", average);        
+        writeln!(out, "average attempts: {}", average)?;
     }
-    println!();
+    writeln!(out)?;
     let sum: u64 = summed_attempt_sequence.par_iter().sum();
 
     let total_average: f64 = sum as f64 / (summed_attempt_sequence.len() as f64 * attemp_sequence[0].len() as f64); // calculate average
 
-    println!("Total Average: {} {}",sum, total_average);
+    writeln!(out, "Total Average: {} {}",sum, total_average)?;
+    Ok(())
 }
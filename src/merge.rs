@@ -0,0 +1,89 @@
+use std::{
+    collections::BTreeSet,
+    path::{Path, PathBuf},
+};
+
+use crate::error::{of_player, StoreError};
+use crate::stats::{Record, Stats};
+use crate::store::{self, Store};
+
+/// What merging state files did.
+pub struct MergeReport {
+    pub added: usize, // games new to the output
+    pub duplicates: usize, // games the output had already, told by their start time
+    pub conflicts: Vec<String>, // games added although they clash with another one
+    pub profiles: Vec<(Option<String>, Stats)>, // every profile of the output as merged, the games played without a name first
+}
+
+// copies of one game have the same start time and the same guesses
+fn same_game(a: &Record, b: &Record) -> bool {
+    a.started_at.is_some() && a.started_at == b.started_at && a.answer == b.answer && a.guesses == b.guesses
+}
+
+/// Adds the games of every profile in `sources` to `output`, created if missing, skipping games it already has.
+/// Games are told apart by their start time; those without one are always added. When every game of a profile
+/// has a start time the profile is stored in the order they were played, otherwise the new games go after the others.
+///
+/// ```
+/// use wordle::merge::merge;
+///
+/// let output = std::env::temp_dir().join(format!("wordle-merge-{}.json", std::process::id()));
+/// let sources = vec!["tests/data/20_profiles.json".into(), "tests/data/20_profiles.json".into()];
+/// let report = merge(&sources, &output).unwrap();
+/// for file in ["json", "json.bak", "json.lock"] {
+///     std::fs::remove_file(output.with_extension(file)).unwrap();
+/// }
+/// // without start times the copies cannot be told apart
+/// assert_eq!((report.added, report.duplicates), (16, 0));
+/// assert_eq!(report.profiles.iter().map(|(player, _)| player.as_deref()).collect::<Vec<_>>(), [None, Some("Ada"), Some("Alan"), Some("Grace")]);
+/// ```
+pub fn merge(sources: &[PathBuf], output: &Path) -> Result<MergeReport, StoreError> {
+    // only read from, a database is not even given the tables it lacks
+    let opened: Vec<Box<dyn Store>> = sources.iter().map(|source| store::open_read_only(source, None)).collect::<Result<_, _>>()?;
+    let mut players: BTreeSet<String> = BTreeSet::new();
+    for source in &opened {
+        players.extend(source.players()?);
+    }
+
+    let mut report = MergeReport { added: 0, duplicates: 0, conflicts: Vec::new(), profiles: Vec::new() };
+    for player in std::iter::once(None).chain(players.iter().map(|player| Some(player.as_str()))) {
+        let of: String = of_player(player);
+        let mut candidates: Vec<(String, Record)> = Vec::new();
+        for (path, source) in sources.iter().zip(&opened) {
+            let games = source.games(player)?.into_iter().enumerate();
+            candidates.extend(games.map(|(i, game)| (format!("{}: game {}{}", path.display(), i + 1, of), game)));
+        }
+        // in the order played when that is known, otherwise file by file
+        if candidates.iter().all(|(_, game)| game.started_at.is_some()) {
+            candidates.sort_by_key(|(_, game)| game.started_at);
+        }
+
+        let mut output_store: Box<dyn Store> = store::open(output, None)?;
+        let mut games: Vec<Record> = output_store.games(player)?;
+        let kept: usize = games.len();
+        for (origin, game) in candidates {
+            if games.iter().any(|other| same_game(other, &game)) {
+                report.duplicates += 1;
+                continue;
+            }
+            if let Some(other) = games.iter().find(|other| other.started_at.is_some() && other.started_at == game.started_at) {
+                report.conflicts.push(format!("{} started at the same time as a game of {}", origin, other.answer));
+            } else if let Some(date) = game.date.filter(|&date| games.iter().any(|other| other.date == Some(date))) {
+                report.conflicts.push(format!("{} is the daily puzzle of {}, played already", origin, date));
+            }
+            games.push(game);
+            report.added += 1;
+        }
+        // older games merged into a newer file go before its own, so that the streaks follow the order played
+        if games.iter().all(|game| game.started_at.is_some()) {
+            games.sort_by_key(|game| game.started_at);
+            output_store.replace(player, &games)?;
+        } else {
+            output_store.append(player, &games[kept..])?;
+        }
+        // counted again in the order stored
+        let merged: Stats = Stats::load(Some(output_store), player)?;
+        report.profiles.push((player.map(str::to_string), merged));
+    }
+    Ok(report)
+}
@@ -0,0 +1,169 @@
+use std::{
+    fs,
+    io::{self, Read, Write},
+    path::Path,
+    sync::OnceLock,
+};
+use rayon::prelude::*;
+
+use crate::game::{GuessWordStatus, LetterStatus, ALPHABET_LENGTH};
+use crate::word_list::fingerprint;
+
+/// Longest words whose feedback fits into a `Pattern`.
+pub const MAX_PATTERN_LENGTH: usize = 5;
+/// Distinct patterns for words of `MAX_PATTERN_LENGTH` letters, 3^5.
+pub const PATTERN_COUNT: usize = 243;
+
+const MATRIX_MAGIC: &[u8; 8] = b"WORDLEPM";
+
+/// Feedback for a whole guess in base 3, the first letter being the least significant digit: red 0, yellow 1, green 2.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Pattern(pub u8);
+
+impl Pattern {
+    /// Same colouring as `game::get_status`, without allocating. Words must not be longer than `MAX_PATTERN_LENGTH`.
+    pub fn new(word: &str, answer: &str) -> Pattern {
+        let word: &[u8] = word.as_bytes();
+        let answer: &[u8] = answer.as_bytes();
+        let mut ans_counter: [u8; ALPHABET_LENGTH] = [0; ALPHABET_LENGTH];
+        let mut digits: [u8; MAX_PATTERN_LENGTH] = [0; MAX_PATTERN_LENGTH];
+
+        for (i, &c) in answer.iter().enumerate() {
+            if word[i] == c {
+                digits[i] = 2;
+            } else {
+                ans_counter[(c - b'A') as usize] += 1;
+            }
+        }
+        for (i, &c) in word.iter().enumerate() {
+            let index: usize = (c - b'A') as usize;
+            if digits[i] != 2 && ans_counter[index] > 0 {
+                ans_counter[index] -= 1;
+                digits[i] = 1;
+            }
+        }
+
+        Pattern(digits.iter().rev().fold(0, |pattern, digit| pattern * 3 + digit))
+    }
+
+    pub fn from_status(status: &[LetterStatus]) -> Pattern {
+        Pattern(status.iter().rev().fold(0, |pattern, letter| {
+            pattern * 3 + match letter {
+                LetterStatus::Green => 2,
+                LetterStatus::Yellow => 1,
+                _ => 0,
+            }
+        }))
+    }
+
+    pub fn to_status(self, word_length: usize) -> GuessWordStatus {
+        let mut pattern: u8 = self.0;
+        (0..word_length)
+            .map(|_| {
+                let digit: u8 = pattern % 3;
+                pattern /= 3;
+                match digit {
+                    2 => LetterStatus::Green,
+                    1 => LetterStatus::Yellow,
+                    _ => LetterStatus::Red,
+                }
+            })
+            .collect()
+    }
+
+    /// Whether every letter of a `word_length` letter guess is green.
+    pub fn is_win(self, word_length: usize) -> bool {
+        self.0 as usize == 3usize.pow(word_length as u32) - 1
+    }
+}
+
+/// The pattern of every guess against every answer, rows built on first use.
+pub struct PatternMatrix {
+    guesses: Vec<String>,
+    answers: Vec<String>,
+    rows: Vec<OnceLock<Vec<Pattern>>>,
+}
+
+impl PatternMatrix {
+    /// An empty matrix; `None` if the words are too long for a `Pattern`.
+    pub fn new(guesses: &[String], answers: &[String]) -> Option<Self> {
+        if guesses.iter().chain(answers).any(|word| word.len() > MAX_PATTERN_LENGTH) {
+            return None;
+        }
+        Some(PatternMatrix {
+            guesses: guesses.to_vec(),
+            answers: answers.to_vec(),
+            rows: (0..guesses.len()).map(|_| OnceLock::new()).collect(),
+        })
+    }
+
+    pub fn guesses(&self) -> &[String] {
+        &self.guesses
+    }
+
+    pub fn answers(&self) -> &[String] {
+        &self.answers
+    }
+
+    /// Patterns of `guesses[guess]` against every answer.
+    pub fn row(&self, guess: usize) -> &[Pattern] {
+        self.rows[guess].get_or_init(|| {
+            let word: &str = &self.guesses[guess];
+            self.answers.iter().map(|answer| Pattern::new(word, answer)).collect()
+        })
+    }
+
+    pub fn get(&self, guess: usize, answer: usize) -> Pattern {
+        self.row(guess)[answer]
+    }
+
+    /// Builds every row that has not been used yet.
+    pub fn fill(&self) {
+        (0..self.rows.len()).into_par_iter().for_each(|guess| {
+            self.row(guess);
+        });
+    }
+
+    /// Reads a matrix written by `save` for the same word lists, building a new one if the file is missing or stale.
+    pub fn load_or_build(path: &Path, guesses: &[String], answers: &[String]) -> io::Result<Option<Self>> {
+        let matrix: PatternMatrix = match Self::new(guesses, answers) {
+            Some(matrix) => matrix,
+            None => return Ok(None),
+        };
+        if path.exists() {
+            let mut content: Vec<u8> = Vec::new();
+            fs::File::open(path)?.read_to_end(&mut content)?;
+            let header: Vec<u8> = matrix.header();
+            let size: usize = header.len() + guesses.len() * answers.len();
+            if content.len() == size && content.starts_with(&header) {
+                for (guess, row) in content[header.len()..].chunks(answers.len().max(1)).enumerate() {
+                    let _ = matrix.rows[guess].set(row.iter().map(|&pattern| Pattern(pattern)).collect());
+                }
+                return Ok(Some(matrix));
+            }
+        }
+        matrix.save(path)?;
+        Ok(Some(matrix))
+    }
+
+    /// Writes the whole matrix, building missing rows first.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        self.fill();
+        let mut file: fs::File = fs::File::create(path)?;
+        file.write_all(&self.header())?;
+        for guess in 0..self.rows.len() {
+            let row: Vec<u8> = self.row(guess).iter().map(|pattern| pattern.0).collect();
+            file.write_all(&row)?;
+        }
+        Ok(())
+    }
+
+    // identifies the word lists, so that a cache of other lists is never used
+    fn header(&self) -> Vec<u8> {
+        let mut header: Vec<u8> = MATRIX_MAGIC.to_vec();
+        header.extend((self.guesses.len() as u64).to_le_bytes());
+        header.extend((self.answers.len() as u64).to_le_bytes());
+        header.extend(fingerprint(&self.guesses, &self.answers).to_le_bytes());
+        header
+    }
+}
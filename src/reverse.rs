@@ -0,0 +1,224 @@
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashSet},
+    fmt,
+};
+use rayon::prelude::*;
+
+use crate::assist::{parse_feedback, AssistError};
+use crate::game::{get_status, GuessWordStatus, LetterStatus};
+use crate::pattern::{Pattern, MAX_PATTERN_LENGTH, PATTERN_COUNT};
+
+/// Why a result block could not be read back.
+#[derive(Debug)]
+pub enum ReverseError {
+    Row(usize, AssistError), // row counting from 1
+    NoRows,
+    TooManyRows(usize), // the attempts every game has
+    WonEarly(usize), // the all green row, counting from 1, that is not the last one
+}
+
+impl fmt::Display for ReverseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Row(row, error) => write!(f, "Row {}: {}", row, error),
+            Self::NoRows => write!(f, "There are no rows of squares in the result block."),
+            Self::TooManyRows(attempts) => write!(f, "A game is over after {} guesses, the result block has more rows.", attempts),
+            Self::WonEarly(row) => write!(f, "Row {} is all green, the game would have ended there.", row),
+        }
+    }
+}
+
+impl std::error::Error for ReverseError {}
+
+/// An answer that fits a result block, with its share of the probability when every guess is picked at random.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Deduction {
+    pub answer: String,
+    pub likelihood: f64,
+}
+
+/// Guesses that give a result block, made by a player who only guesses words that may still be the answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sequence {
+    pub guesses: Vec<String>, // one per row, the last one is the answer when the block is won
+    pub odds: u64, // such a player makes these guesses once in `odds` games whose feedback is the same
+}
+
+/// The rows of squares of a result block like the one `share::share_text` writes; the title and blank lines are skipped.
+/// Rows may also be typed as `g`, `y` and `b` like in assist mode.
+///
+/// ```
+/// use wordle::game::LetterStatus::{Green, Red, Yellow};
+/// use wordle::reverse::parse_grid;
+///
+/// let rows = parse_grid("Wordle 123 2/6*\n\n⬛🟩🟩🟨🟩\n🟩🟩🟩🟩🟩\n", 5, 6).unwrap();
+/// assert_eq!(rows, [vec![Red, Green, Green, Yellow, Green], vec![Green; 5]]);
+/// assert!(parse_grid("🟩🟩🟩🟩🟩\nbbbbb", 5, 6).is_err());
+/// ```
+pub fn parse_grid(text: &str, word_length: usize, max_attempts: usize) -> Result<Vec<GuessWordStatus>, ReverseError> {
+    let mut rows: Vec<GuessWordStatus> = Vec::new();
+    for line in text.lines().map(str::trim) {
+        // the title has letters other than feedback, rows are squares or feedback letters only
+        let is_row: bool = line.chars().any(|c| "🟩🟨⬛⬜🟧🟦".contains(c)) || (!line.is_empty() && line.chars().all(|c| "gybxGYBX-".contains(c)));
+        if !is_row {
+            continue;
+        }
+        let row: GuessWordStatus = parse_feedback(line, word_length).map_err(|error| ReverseError::Row(rows.len() + 1, error))?;
+        if rows.last().is_some_and(is_win) {
+            return Err(ReverseError::WonEarly(rows.len()));
+        }
+        rows.push(row);
+    }
+    if rows.is_empty() {
+        return Err(ReverseError::NoRows);
+    }
+    if rows.len() > max_attempts {
+        return Err(ReverseError::TooManyRows(max_attempts));
+    }
+    Ok(rows)
+}
+
+fn is_win(row: &GuessWordStatus) -> bool {
+    row.iter().all(|&letter| letter == LetterStatus::Green)
+}
+
+// feedback as a number in base 3 like a `Pattern`, for words of any length
+fn status_index(status: &[LetterStatus]) -> usize {
+    status.iter().rev().fold(0, |index, letter| {
+        index * 3 + match letter {
+            LetterStatus::Green => 2,
+            LetterStatus::Yellow => 1,
+            _ => 0,
+        }
+    })
+}
+
+// feedback of `guess` against `answer` as a number to compare, the fast `Pattern` when the words are short enough
+fn feedback(guess: &str, answer: &str) -> usize {
+    if guess.len() <= MAX_PATTERN_LENGTH {
+        Pattern::new(guess, answer).0 as usize
+    } else {
+        status_index(&get_status(guess, answer))
+    }
+}
+
+/// The answers for which every row of `rows` is the feedback of some word in `guesses`, most likely first.
+/// An answer is as likely as the chance that guesses picked at random from `guesses` give all rows.
+///
+/// ```
+/// use wordle::game::get_status;
+/// use wordle::reverse::deduce;
+///
+/// let words: Vec<String> = ["CRANE", "CRATE", "GHOST", "SLATE"].iter().map(|word| word.to_string()).collect();
+/// let rows = vec![get_status("SLATE", "CRATE"), get_status("CRATE", "CRATE")];
+/// let deductions = deduce(&rows, &words, &words);
+/// // SLATE against CRATE gives the first row, and so does CRATE against SLATE
+/// let answers: Vec<(&str, f64)> = deductions.iter().map(|deduction| (deduction.answer.as_str(), deduction.likelihood)).collect();
+/// assert_eq!(answers, [("CRATE", 0.5), ("SLATE", 0.5)]);
+/// ```
+pub fn deduce(rows: &[GuessWordStatus], answers: &[String], guesses: &[String]) -> Vec<Deduction> {
+    let wanted: Vec<usize> = rows.iter().map(|row| status_index(row)).collect();
+    let long: bool = answers.iter().any(|answer| answer.len() > MAX_PATTERN_LENGTH);
+    let weights: Vec<(usize, f64)> = answers
+        .par_iter()
+        .enumerate()
+        .filter_map(|(index, answer)| {
+            // how many guesses give each feedback against this answer, a single pass for all rows
+            let mut counts: Vec<usize> = vec![0; if long { 3usize.pow(answer.len() as u32) } else { PATTERN_COUNT }];
+            for guess in guesses {
+                counts[feedback(guess, answer)] += 1;
+            }
+            let weight: f64 = wanted.iter().map(|&pattern| counts[pattern] as f64).product();
+            (weight > 0.0).then_some((index, weight))
+        })
+        .collect();
+
+    let total: f64 = weights.iter().map(|(_, weight)| weight).sum();
+    let mut deductions: Vec<Deduction> =
+        weights.into_iter().map(|(index, weight)| Deduction { answer: answers[index].clone(), likelihood: weight / total }).collect();
+    // stable, so equally likely answers stay in the order of the list
+    deductions.sort_by(|a, b| b.likelihood.partial_cmp(&a.likelihood).unwrap());
+    deductions
+}
+
+// guesses made so far, with the words that still fit all their feedback
+struct Partial {
+    odds: u64,
+    guesses: Vec<usize>,
+    candidates: Vec<usize>,
+}
+
+// the heap pops the lowest odds first, then the guesses earliest in the word list
+impl Ord for Partial {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.odds.cmp(&self.odds).then_with(|| other.guesses.cmp(&self.guesses))
+    }
+}
+
+impl PartialOrd for Partial {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Partial {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Partial {}
+
+/// Up to `count` guess sequences that give `rows` for one of `answers`, most likely first, by a player who picks every
+/// guess at random among the words of `words` that still fit all the feedback so far; answers missing from `words` have none.
+///
+/// ```
+/// use wordle::game::get_status;
+/// use wordle::reverse::sequences;
+///
+/// let words: Vec<String> = ["CRANE", "CRATE", "GHOST", "SLATE"].iter().map(|word| word.to_string()).collect();
+/// let rows = vec![get_status("SLATE", "CRATE"), get_status("CRATE", "CRATE")];
+/// let found = sequences(&rows, &["CRATE".to_string()], &words, 5);
+/// // SLATE is one of 4 words to start with, then CRATE the only word left
+/// assert_eq!(found.len(), 1);
+/// assert_eq!((found[0].guesses.join(" "), found[0].odds), ("SLATE CRATE".to_string(), 4));
+/// ```
+pub fn sequences(rows: &[GuessWordStatus], answers: &[String], words: &[String], count: usize) -> Vec<Sequence> {
+    let wanted: Vec<usize> = rows.iter().map(|row| status_index(row)).collect();
+    let allowed: HashSet<&String> = answers.iter().collect();
+    let mut heap: BinaryHeap<Partial> = BinaryHeap::new();
+    heap.push(Partial { odds: 1, guesses: Vec::new(), candidates: (0..words.len()).collect() });
+
+    // the words left depend on the guesses and the rows only, not on the answer; every guess multiplies the odds,
+    // so the first complete sequences popped are the most likely ones
+    let mut found: Vec<Sequence> = Vec::new();
+    while let Some(partial) = heap.pop() {
+        if found.len() >= count {
+            break;
+        }
+        let turn: usize = partial.guesses.len();
+        if turn == rows.len() {
+            found.push(Sequence { guesses: partial.guesses.iter().map(|&guess| words[guess].clone()).collect(), odds: partial.odds });
+            continue;
+        }
+        let odds: u64 = partial.odds.saturating_mul(partial.candidates.len() as u64);
+        let children: Vec<Partial> = partial
+            .candidates
+            .par_iter()
+            .filter_map(|&guess| {
+                let candidates: Vec<usize> =
+                    partial.candidates.iter().copied().filter(|&word| feedback(&words[guess], &words[word]) == wanted[turn]).collect();
+                // some answer of interest must still fit
+                if !candidates.iter().any(|&word| allowed.contains(&words[word])) {
+                    return None;
+                }
+                let mut guesses: Vec<usize> = partial.guesses.clone();
+                guesses.push(guess);
+                Some(Partial { odds, guesses, candidates })
+            })
+            .collect();
+        heap.extend(children);
+    }
+    found
+}
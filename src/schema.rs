@@ -0,0 +1,88 @@
+use std::path::Path;
+
+use serde_json::{Map, Value};
+
+use crate::error::{of_player, StateError};
+
+/// The layout of the state files written now; files without a `schema_version` are version 0.
+pub const SCHEMA_VERSION: u64 = 1;
+
+type Migration = fn(&mut Map<String, Value>, &mut Vec<String>);
+
+// step `i` upgrades version `i` to `i + 1`
+const MIGRATIONS: [Migration; SCHEMA_VERSION as usize] = [to_version_1];
+
+/// The schema version of the state read from `path`.
+pub fn version(path: &Path, state: &Value) -> Result<u64, StateError> {
+    match state.get("schema_version") {
+        None => Ok(0),
+        Some(version) => version.as_u64().ok_or_else(|| StateError::UnknownVersion { path: path.to_path_buf(), version: version.to_string() }),
+    }
+}
+
+/// Upgrades the state read from `path` to `SCHEMA_VERSION` in place, describing every change.
+///
+/// ```
+/// use std::path::Path;
+/// use wordle::schema::{migrate, version, SCHEMA_VERSION};
+///
+/// // a state file that has never been saved to
+/// let mut state = serde_json::json!({});
+/// let changes = migrate(Path::new("state.json"), &mut state).unwrap();
+/// assert_eq!(changes, ["games: added an empty list", "total_rounds: set to 0"]);
+/// assert_eq!(version(Path::new("state.json"), &state).unwrap(), SCHEMA_VERSION);
+/// assert!(migrate(Path::new("state.json"), &mut state).unwrap().is_empty());
+///
+/// // games counted by total_rounds only gain the version
+/// let mut state = serde_json::json!({ "total_rounds": 1, "games": [{ "answer": "CRANE", "guesses": ["CRANE"] }] });
+/// assert!(migrate(Path::new("state.json"), &mut state).unwrap().is_empty());
+/// assert_eq!(state["schema_version"], SCHEMA_VERSION);
+/// ```
+pub fn migrate(path: &Path, state: &mut Value) -> Result<Vec<String>, StateError> {
+    let found: u64 = version(path, state)?;
+    if found > SCHEMA_VERSION {
+        return Err(StateError::NewerVersion { path: path.to_path_buf(), version: found, supported: SCHEMA_VERSION });
+    }
+    let Some(state) = state.as_object_mut() else {
+        return Err(StateError::InvalidLayout { path: path.to_path_buf(), message: "the state should be a JSON object".to_string() });
+    };
+
+    let mut changes: Vec<String> = Vec::new();
+    for migration in &MIGRATIONS[found as usize..] {
+        migration(state, &mut changes);
+    }
+    state.insert("schema_version".to_string(), SCHEMA_VERSION.into());
+    Ok(changes)
+}
+
+// the first versioned layout: every profile has a game list counted by `total_rounds`, which unversioned files
+// left out until the first game was saved
+fn to_version_1(state: &mut Map<String, Value>, changes: &mut Vec<String>) {
+    upgrade_profile(state, None, changes);
+    if let Some(players) = state.get_mut("players").and_then(Value::as_object_mut) {
+        for (name, profile) in players.iter_mut() {
+            if let Some(profile) = profile.as_object_mut() {
+                upgrade_profile(profile, Some(name), changes);
+            }
+        }
+    }
+}
+
+fn upgrade_profile(profile: &mut Map<String, Value>, player: Option<&str>, changes: &mut Vec<String>) {
+    let of: String = of_player(player);
+    let games: &mut Value = profile.entry("games").or_insert(Value::Null);
+    if games.is_null() {
+        *games = Value::Array(Vec::new());
+        changes.push(format!("games{}: added an empty list", of));
+    }
+    // anything else is left for loading to reject
+    let Some(games) = games.as_array() else {
+        return;
+    };
+
+    let count: usize = games.len();
+    if profile.get("total_rounds").and_then(Value::as_u64) != Some(count as u64) {
+        profile.insert("total_rounds".to_string(), count.into());
+        changes.push(format!("total_rounds{}: set to {}", of, count));
+    }
+}
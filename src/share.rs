@@ -0,0 +1,50 @@
+use crate::game::{Game, GuessWordStatus, LetterStatus};
+
+/// Squares of a share block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Palette {
+    /// Green for the right place, yellow for elsewhere.
+    #[default]
+    Standard,
+    /// Orange and blue, told apart without colour vision.
+    HighContrast,
+}
+
+impl Palette {
+    fn square(self, status: LetterStatus) -> char {
+        match (self, status) {
+            (Self::Standard, LetterStatus::Green) => '🟩',
+            (Self::Standard, LetterStatus::Yellow) => '🟨',
+            (Self::HighContrast, LetterStatus::Green) => '🟧',
+            (Self::HighContrast, LetterStatus::Yellow) => '🟦',
+            _ => '⬛',
+        }
+    }
+}
+
+/// A result block like `Wordle 123 4/6*` over one row of squares per guess, without the words.
+/// `X` stands for the count of a lost game and `*` marks hard mode.
+///
+/// ```
+/// use wordle::game::get_status;
+/// use wordle::share::{share_text, Palette};
+///
+/// let rows = vec![get_status("TRACE", "CRANE"), get_status("CRANE", "CRANE")];
+/// assert_eq!(share_text("Wordle 123", &rows, true, 6, true, Palette::Standard), "Wordle 123 2/6*\n\n⬛🟩🟩🟨🟩\n🟩🟩🟩🟩🟩");
+/// ```
+pub fn share_text(title: &str, rows: &[GuessWordStatus], won: bool, max_attempts: usize, hard: bool, palette: Palette) -> String {
+    let tries: String = if won { rows.len().to_string() } else { "X".to_string() };
+    let mut text: String = format!("{} {}/{}{}\n", title, tries, max_attempts, if hard { "*" } else { "" });
+    for row in rows {
+        text.push('\n');
+        text.extend(row.iter().map(|&status| palette.square(status)));
+    }
+    text
+}
+
+/// The result block of a finished `game`, titled with its puzzle number in daily mode.
+pub fn share_game(game: &Game, palette: Palette) -> String {
+    let title: String = game.daily.map_or("Wordle".to_string(), |daily| daily.to_string());
+    let rows: Vec<GuessWordStatus> = game.guesses.iter().map(|(_, status)| status.clone()).collect();
+    share_text(&title, &rows, game.is_win, game.max_attempts, game.is_hard_mode(), palette)
+}
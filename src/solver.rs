@@ -0,0 +1,273 @@
+use std::{
+    collections::{BTreeMap, HashSet},
+    fmt,
+    str::FromStr,
+};
+use rayon::prelude::*;
+use serde::Deserialize;
+
+use crate::constraints::Constraints;
+use crate::game::{get_status, GuessWordStatus, ALPHABET_LENGTH};
+use crate::pattern::{Pattern, PatternMatrix, MAX_PATTERN_LENGTH, PATTERN_COUNT};
+
+// answers whose guess count is still open, as (slot in the counts, answer)
+type Pending = Vec<(usize, usize)>;
+
+/// How the recommender ranks the next guess.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Strategy {
+    /// Sum of the per-position letter frequencies among the candidates; only candidates are ranked.
+    #[default]
+    Frequency,
+    /// Expected information of the feedback in bits, the entropy of the pattern distribution over the candidates.
+    Entropy,
+    /// Fewest candidates left in the worst case, the size of the largest pattern bucket.
+    Minimax,
+}
+
+/// Every strategy, in the order they are listed to the user.
+pub const STRATEGIES: [Strategy; 3] = [Strategy::Frequency, Strategy::Entropy, Strategy::Minimax];
+
+impl FromStr for Strategy {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name.to_lowercase().as_str() {
+            "frequency" => Ok(Self::Frequency),
+            "entropy" => Ok(Self::Entropy),
+            "minimax" => Ok(Self::Minimax),
+            _ => Err(format!("unknown strategy \"{}\", expected frequency, entropy or minimax", name)),
+        }
+    }
+}
+
+impl fmt::Display for Strategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Frequency => write!(f, "frequency"),
+            Self::Entropy => write!(f, "entropy"),
+            Self::Minimax => write!(f, "minimax"),
+        }
+    }
+}
+
+impl Strategy {
+    /// Up to `count` guesses, best first. `probes` may add words that can no longer be the answer but narrow the candidates down further.
+    pub fn recommend(&self, candidates: &[String], probes: &[String], count: usize) -> Vec<String> {
+        let mut grades: Vec<(&String, f64)> = match self {
+            Self::Frequency => {
+                let weights: Vec<[f64; ALPHABET_LENGTH]> = letter_weights(candidates, candidates.len());
+                candidates.par_iter().map(|word| (word, frequency_grade(&weights, word))).collect()
+            }
+            Self::Entropy | Self::Minimax => {
+                // with one or two candidates left guessing one of them is never worse
+                if candidates.len() <= 2 {
+                    return candidates.iter().take(count).cloned().collect();
+                }
+                let candidate_set: HashSet<&String> = candidates.iter().collect();
+                candidates
+                    .par_iter()
+                    .chain(probes.par_iter().filter(|word| !candidate_set.contains(word)))
+                    .map(|word| (word, self.split_grade(bucket_sizes(word, candidates).into_iter(), candidates.len())))
+                    .collect()
+            }
+        };
+
+        // stable, so candidates listed first win ties against probes
+        grades.par_sort_by(|(_, grade1), (_, grade2)| grade2.partial_cmp(grade1).unwrap());
+        grades.into_iter().take(count).map(|(word, _)| word.clone()).collect()
+    }
+
+    /// Guesses `Game::over_all_game_numbers` needs for each of `answers` after starting with `start`, all of them
+    /// indices into the square `matrix` whose words are both the candidates and the probes.
+    pub fn count_guesses(&self, matrix: &PatternMatrix, probes: bool, difficult: bool, start: usize, answers: &[usize]) -> Vec<u64> {
+        let mut walk: Walk = Walk { strategy: *self, matrix, probes, difficult, counts: vec![0; answers.len()] };
+        let everything: Vec<usize> = (0..matrix.answers().len()).collect();
+        let mut pending: Pending = Vec::new();
+        for (slot, &answer) in answers.iter().enumerate() {
+            if answer == start {
+                walk.counts[slot] = 1;
+            } else {
+                pending.push((slot, answer));
+            }
+        }
+        let word_length: usize = matrix.guesses()[start].len();
+        walk.descend(start, &everything, pending, 2, &Constraints::new(word_length));
+        walk.counts
+    }
+
+    // how good a split of `total` candidates into buckets of `sizes` is, higher is better
+    fn split_grade<I: Iterator<Item = usize>>(&self, sizes: I, total: usize) -> f64 {
+        match self {
+            Self::Minimax => -(sizes.max().unwrap_or(0) as f64),
+            _ => bucket_entropy(sizes, total),
+        }
+    }
+}
+
+// the decision tree below one first guess, followed only where an answer of interest is left
+struct Walk<'a> {
+    strategy: Strategy,
+    matrix: &'a PatternMatrix,
+    probes: bool,
+    difficult: bool,
+    counts: Vec<u64>,
+}
+
+impl Walk<'_> {
+    // split the candidates by the feedback for `guess`, `constraints` being what was known before it
+    fn descend(&mut self, guess: usize, candidates: &[usize], pending: Pending, num: u64, constraints: &Constraints) {
+        let row: &[Pattern] = self.matrix.row(guess);
+        // one group of candidates per feedback that an answer of interest gives, filled in a single pass
+        let mut group_of: [usize; PATTERN_COUNT] = [usize::MAX; PATTERN_COUNT];
+        let mut groups: Vec<(Pattern, Vec<usize>, Pending)> = Vec::new();
+        for (slot, answer) in pending {
+            let pattern: usize = row[answer].0 as usize;
+            if group_of[pattern] == usize::MAX {
+                group_of[pattern] = groups.len();
+                groups.push((row[answer], Vec::new(), Vec::new()));
+            }
+            groups[group_of[pattern]].2.push((slot, answer));
+        }
+        for &word in candidates {
+            if let Some((_, next_candidates, _)) = groups.get_mut(group_of[row[word].0 as usize]) {
+                next_candidates.push(word);
+            }
+        }
+
+        let guess_word: &str = &self.matrix.guesses()[guess];
+        for (pattern, next_candidates, group) in groups {
+            // the same constraints `Game::play` keeps, for the difficult mode rule
+            let mut next_constraints: Constraints = constraints.clone();
+            next_constraints.add(guess_word, &pattern.to_status(guess_word.len()));
+
+            let legal = |word: usize| !self.difficult || next_constraints.uses_hints(&self.matrix.answers()[word]);
+            let next_guess: usize = self.best_guess(&next_candidates, legal);
+            let mut pending: Pending = Vec::new();
+            for (slot, answer) in group {
+                if answer == next_guess {
+                    self.counts[slot] = num;
+                } else {
+                    pending.push((slot, answer));
+                }
+            }
+            if !pending.is_empty() {
+                self.descend(next_guess, &next_candidates, pending, num + 1, &next_constraints);
+            }
+        }
+    }
+
+    // the first word `recommend` would return, by index; candidates are always legal, probes only if `legal` says so
+    fn best_guess<F: Fn(usize) -> bool + Sync>(&self, candidates: &[usize], legal: F) -> usize {
+        let words: &[String] = self.matrix.answers();
+        let mut best: (usize, f64) = (candidates[0], f64::NEG_INFINITY);
+        let mut consider = |word: usize, grade: f64| {
+            // only strictly better grades replace, like the stable sort in `recommend`
+            if grade > best.1 {
+                best = (word, grade);
+            }
+        };
+        match self.strategy {
+            Strategy::Frequency => {
+                let weights: Vec<[f64; ALPHABET_LENGTH]> = letter_weights(candidates.iter().map(|&word| &words[word]), candidates.len());
+                for &word in candidates {
+                    consider(word, frequency_grade(&weights, &words[word]));
+                }
+            }
+            Strategy::Entropy | Strategy::Minimax => {
+                if candidates.len() <= 2 {
+                    return candidates[0];
+                }
+                let grade_of = |guess: usize| {
+                    let row: &[Pattern] = self.matrix.row(guess);
+                    let mut counts: [usize; PATTERN_COUNT] = [0; PATTERN_COUNT];
+                    for &answer in candidates {
+                        counts[row[answer].0 as usize] += 1;
+                    }
+                    self.strategy.split_grade(counts.into_iter(), candidates.len())
+                };
+                let grades: Vec<(usize, f64)> = candidates
+                    .par_iter()
+                    .copied()
+                    .chain((0..words.len()).into_par_iter().filter(|&word| self.probes && candidates.binary_search(&word).is_err() && legal(word)))
+                    .map(|word| (word, grade_of(word)))
+                    .collect();
+                for (word, grade) in grades {
+                    consider(word, grade);
+                }
+            }
+        }
+        best.0
+    }
+}
+
+// share of the candidates with each letter at each position, indexed as [position][letter]
+fn letter_weights<'a, I: IntoIterator<Item = &'a String>>(candidates: I, count: usize) -> Vec<[f64; ALPHABET_LENGTH]> {
+    let mut weights: Vec<[f64; ALPHABET_LENGTH]> = Vec::new();
+    for word in candidates {
+        weights.resize(word.len(), [0.0; ALPHABET_LENGTH]);
+        for (position, c) in word.bytes().enumerate() {
+            weights[position][(c - b'A') as usize] += 1.0;
+        }
+    }
+    // normalization
+    for position in weights.iter_mut() {
+        for weight in position.iter_mut() {
+            *weight /= count as f64;
+        }
+    }
+    weights
+}
+
+fn frequency_grade(weights: &[[f64; ALPHABET_LENGTH]], word: &str) -> f64 {
+    word.bytes().enumerate().map(|(position, c)| weights[position][(c - b'A') as usize]).sum()
+}
+
+/// Expected information in bits from guessing `guess` when every candidate is equally likely to be the answer.
+pub fn entropy(guess: &str, candidates: &[String]) -> f64 {
+    bucket_entropy(bucket_sizes(guess, candidates).into_iter(), candidates.len())
+}
+
+/// Candidates expected to be left after guessing `guess`, the answer among them, when every candidate is equally likely.
+///
+/// ```
+/// use wordle::solver::expected_left;
+///
+/// let candidates: Vec<String> = ["CRANE", "BRINE", "GHOST"].iter().map(|word| word.to_string()).collect();
+/// // CRANE and BRINE give the same feedback to GHOST: (2 * 2 + 1 * 1) / 3
+/// assert_eq!(expected_left("GHOST", &candidates), 5.0 / 3.0);
+/// assert_eq!(expected_left("CRANE", &candidates), 1.0);
+/// ```
+pub fn expected_left(guess: &str, candidates: &[String]) -> f64 {
+    let squares: usize = bucket_sizes(guess, candidates).into_iter().map(|size| size * size).sum();
+    squares as f64 / candidates.len().max(1) as f64
+}
+
+// how many candidates give each feedback, in a fixed order; empty buckets may be included
+fn bucket_sizes(guess: &str, candidates: &[String]) -> Vec<usize> {
+    if guess.len() <= MAX_PATTERN_LENGTH {
+        let mut counts: Vec<usize> = vec![0; PATTERN_COUNT];
+        for answer in candidates {
+            counts[Pattern::new(guess, answer).0 as usize] += 1;
+        }
+        counts
+    } else {
+        let mut buckets: BTreeMap<GuessWordStatus, usize> = BTreeMap::new();
+        for answer in candidates {
+            *buckets.entry(get_status(guess, answer)).or_insert(0) += 1;
+        }
+        buckets.into_values().collect()
+    }
+}
+
+// summed in a fixed order so that equal splits always grade exactly the same
+fn bucket_entropy<I: Iterator<Item = usize>>(sizes: I, total: usize) -> f64 {
+    sizes
+        .filter(|&size| size > 0)
+        .map(|size| {
+            let p: f64 = size as f64 / total as f64;
+            -p * p.log2()
+        })
+        .sum()
+}
@@ -0,0 +1,359 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use chrono::NaiveDate;
+use rusqlite::{params, Connection, OpenFlags, Row, Transaction};
+
+use crate::error::{of_player, StoreError};
+use crate::game::DEFAULT_MAX_ATTEMPTS;
+use crate::stats::{word_lists_name, Record, Totals, WordLists};
+use crate::store::{Filter, Store};
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS players (
+        name TEXT PRIMARY KEY
+    );
+    CREATE TABLE IF NOT EXISTS games (
+        id INTEGER PRIMARY KEY, -- in the order played
+        player TEXT REFERENCES players (name), -- NULL for games played without a name
+        answer TEXT NOT NULL,
+        won INTEGER NOT NULL,
+        max_attempts INTEGER,
+        date TEXT,
+        puzzle INTEGER,
+        difficult INTEGER NOT NULL,
+        strict INTEGER NOT NULL,
+        random INTEGER NOT NULL,
+        seed INTEGER,
+        day INTEGER,
+        final_set TEXT,
+        acceptable_set TEXT,
+        fingerprint TEXT,
+        started_at TEXT,
+        duration_secs INTEGER,
+        hints_shown INTEGER NOT NULL,
+        recommendations_shown INTEGER NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS games_of_player ON games (player, id);
+    CREATE TABLE IF NOT EXISTS guesses (
+        game INTEGER NOT NULL REFERENCES games (id),
+        turn INTEGER NOT NULL, -- from 1
+        word TEXT NOT NULL,
+        PRIMARY KEY (game, turn)
+    );
+";
+
+// the games of player ?1 numbered among all of them, narrowed down by won ?2, hard ?3 and answer ?4 unless NULL
+const FIND: &str = "
+    SELECT * FROM (
+        SELECT ROW_NUMBER() OVER (ORDER BY id) AS number, * FROM games WHERE player IS ?1
+    )
+    WHERE (?2 IS NULL OR won = ?2) AND (?3 IS NULL OR (difficult OR strict) = ?3) AND (?4 IS NULL OR answer = ?4)
+    ORDER BY id
+";
+
+// every game of player ?1 numbered among all of them, a row for each of its guesses in the order played
+const GAMES_AND_GUESSES: &str = "
+    SELECT DENSE_RANK() OVER (ORDER BY id) AS number, games.*, word
+    FROM games LEFT JOIN guesses ON game = id
+    WHERE player IS ?1
+    ORDER BY id, turn
+";
+
+// the games of player ?1 grouped by everything the totals count them by, with how many there are in each group
+const GROUPS: &str = "
+    SELECT strict, difficult, final_set, acceptable_set, won AND tries IS NOT NULL, max_attempts, COALESCE(tries, 0), COUNT(*)
+    FROM games LEFT JOIN (SELECT game, COUNT(*) AS tries FROM guesses GROUP BY game) ON game = id
+    WHERE player IS ?1
+    GROUP BY 1, 2, 3, 4, 5, 6, 7
+";
+
+// how often player ?1 guessed each word
+const WORDS: &str = "
+    SELECT word, COUNT(*) FROM guesses JOIN games ON game = id WHERE player IS ?1 GROUP BY word
+";
+
+// the wins of player ?1 since the last lost game, and the longest run of wins between two lost games
+const STREAKS: &str = "
+    SELECT
+        COALESCE(SUM(won AND losses = (SELECT COUNT(*) FROM games WHERE player IS ?1 AND NOT won)), 0),
+        COALESCE(MAX(length), 0)
+    FROM (
+        SELECT won, losses, SUM(won) OVER (PARTITION BY losses) AS length
+        FROM (SELECT won, SUM(NOT won) OVER (ORDER BY id) AS losses FROM games WHERE player IS ?1)
+    )
+";
+
+/// The history in an SQLite database: players, games and their guesses in tables of their own,
+/// so that saving only adds a game and looking for games is a query. The totals are counted by queries too.
+///
+/// ```
+/// use wordle::sqlite_store::SqliteStore;
+/// use wordle::stats::{Record, Totals};
+/// use wordle::store::Store;
+///
+/// let games: Vec<Record> = serde_json::from_str(r#"[
+///     {"answer": "CRANE", "guesses": ["CRANE"]},
+///     {"answer": "GHOST", "guesses": ["CRANE", "SLATE", "PILOT", "MOIST", "FROST", "ROOST"]},
+///     {"answer": "SLATE", "guesses": ["CRANE", "SLATE"]},
+///     {"answer": "PIANO", "guesses": ["AUDIO", "PIANO"], "difficult": true},
+///     {"answer": "HELLO", "guesses": ["CRANE", "AUDIO", "PILOT", "MOIST", "FROST", "ROOST"], "strict": true},
+///     {"answer": "WORLD", "guesses": ["CRANE", "WORLD"]}
+/// ]"#).unwrap();
+/// let path = std::env::temp_dir().join("wordle_sqlite_totals.db");
+/// let _ = std::fs::remove_file(&path);
+/// let mut store = SqliteStore::open(&path).unwrap();
+/// store.append(Some("Ada"), &games).unwrap();
+/// let totals: Totals = store.totals(Some("Ada")).unwrap();
+/// assert_eq!(totals, Totals::of(&games));
+/// assert_eq!((totals.current_streak, totals.max_streak), (1, 2));
+///
+/// // a game added by something else, with a guess too short for its answer, is refused rather than counted
+/// let connection = rusqlite::Connection::open(&path).unwrap();
+/// connection.execute_batch("
+///     INSERT INTO games (player, answer, won, difficult, strict, random, hints_shown, recommendations_shown)
+///     VALUES ('Ada', 'GHOST', 0, 0, 0, 0, 0, 0);
+///     INSERT INTO guesses (game, turn, word) VALUES (last_insert_rowid(), 1, 'CRAN');
+/// ").unwrap();
+/// let error = store.totals(Some("Ada")).unwrap_err();
+/// assert!(error.to_string().ends_with("game 7 of Ada cannot have been played: the guess CRAN does not fit the answer GHOST"));
+/// ```
+pub struct SqliteStore {
+    path: PathBuf,
+    connection: Connection,
+}
+
+impl SqliteStore {
+    /// Creates the tables if the database is new.
+    pub fn open(path: &Path) -> Result<Self, StoreError> {
+        let database = |source: rusqlite::Error| StoreError::Database { path: path.to_path_buf(), source };
+        let connection: Connection = Connection::open(path).map_err(database)?;
+        // another session may be adding a game
+        connection.busy_timeout(Duration::from_secs(5)).map_err(database)?;
+        connection.execute_batch(SCHEMA).map_err(database)?;
+        Ok(SqliteStore { path: path.to_path_buf(), connection })
+    }
+
+    /// Opens an existing database without creating anything in it; saving to it fails.
+    pub fn open_read_only(path: &Path) -> Result<Self, StoreError> {
+        let database = |source: rusqlite::Error| StoreError::Database { path: path.to_path_buf(), source };
+        let connection: Connection = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY).map_err(database)?;
+        connection.busy_timeout(Duration::from_secs(5)).map_err(database)?;
+        Ok(SqliteStore { path: path.to_path_buf(), connection })
+    }
+
+    fn error(&self, source: rusqlite::Error) -> StoreError {
+        StoreError::Database { path: self.path.clone(), source }
+    }
+
+    fn insert(transaction: &Transaction, player: Option<&str>, game: &Record) -> rusqlite::Result<()> {
+        let word_lists: Option<&WordLists> = game.word_lists.as_ref();
+        transaction.execute(
+            "INSERT INTO games (player, answer, won, max_attempts, date, puzzle, difficult, strict, random, seed, day,
+                final_set, acceptable_set, fingerprint, started_at, duration_secs, hints_shown, recommendations_shown)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18)",
+            params![
+                player,
+                game.answer,
+                game.is_win(),
+                game.max_attempts,
+                game.date,
+                game.puzzle,
+                game.difficult,
+                game.strict,
+                game.random,
+                game.seed.map(|seed| seed as i64), // all 64 bits, read back the same way
+                game.day,
+                word_lists.map(|lists| &lists.final_set),
+                word_lists.map(|lists| &lists.acceptable_set),
+                word_lists.map(|lists| &lists.fingerprint),
+                game.started_at,
+                game.duration_secs,
+                game.hints_shown,
+                game.recommendations_shown,
+            ],
+        )?;
+        let id: i64 = transaction.last_insert_rowid();
+        for (turn, word) in game.guesses.iter().enumerate() {
+            transaction.execute("INSERT INTO guesses (game, turn, word) VALUES (?1, ?2, ?3)", params![id, turn + 1, word])?;
+        }
+        Ok(())
+    }
+
+    // everything about a game but its guesses, along with its id and number
+    fn record(row: &Row) -> rusqlite::Result<(i64, usize, Record)> {
+        let final_set: Option<String> = row.get("final_set")?;
+        let word_lists: Option<WordLists> = match final_set {
+            Some(final_set) => Some(WordLists { final_set, acceptable_set: row.get("acceptable_set")?, fingerprint: row.get("fingerprint")? }),
+            None => None,
+        };
+        let record = Record {
+            answer: row.get("answer")?,
+            guesses: Vec::new(),
+            max_attempts: row.get("max_attempts")?,
+            date: row.get("date")?,
+            puzzle: row.get("puzzle")?,
+            difficult: row.get("difficult")?,
+            strict: row.get("strict")?,
+            random: row.get("random")?,
+            seed: row.get::<_, Option<i64>>("seed")?.map(|seed| seed as u64),
+            day: row.get("day")?,
+            word_lists,
+            started_at: row.get("started_at")?,
+            duration_secs: row.get("duration_secs")?,
+            hints_shown: row.get("hints_shown")?,
+            recommendations_shown: row.get("recommendations_shown")?,
+        };
+        Ok((row.get("id")?, row.get("number")?, record))
+    }
+
+    // the games found by `query`, unless one of them cannot have been played
+    fn checked(&self, player: Option<&str>, filter: &Filter) -> Result<Vec<(usize, Record)>, StoreError> {
+        let games: Vec<(usize, Record)> = self.query(player, filter).map_err(|source| self.error(source))?;
+        if let Some((number, problem)) = games.iter().find_map(|(number, game)| Some((*number, game.problem()?))) {
+            return Err(self.impossible(player, number, problem));
+        }
+        Ok(games)
+    }
+
+    fn impossible(&self, player: Option<&str>, number: usize, problem: String) -> StoreError {
+        let of: String = of_player(player);
+        StoreError::ImpossibleGame { path: self.path.clone(), game: format!("game {}{}", number, of), problem }
+    }
+
+    // the first game of `player` that cannot have been played, read one game at a time
+    fn first_problem(&self, player: Option<&str>) -> rusqlite::Result<Option<(usize, String)>> {
+        let mut statement = self.connection.prepare(GAMES_AND_GUESSES)?;
+        let mut rows = statement.query(params![player])?;
+        let mut current: Option<(i64, usize, Record)> = None;
+        while let Some(row) = rows.next()? {
+            let id: i64 = row.get("id")?;
+            if let Some((_, number, record)) = current.take_if(|(last, _, _)| *last != id) {
+                if let Some(problem) = record.problem() {
+                    return Ok(Some((number, problem)));
+                }
+            }
+            if current.is_none() {
+                current = Some(Self::record(row)?);
+            }
+            if let (Some((_, _, record)), Some(word)) = (&mut current, row.get::<_, Option<String>>("word")?) {
+                record.guesses.push(word);
+            }
+        }
+        Ok(current.and_then(|(_, number, record)| Some((number, record.problem()?))))
+    }
+
+    fn count(&self, player: Option<&str>) -> rusqlite::Result<Totals> {
+        let mut totals = Totals::default();
+        let mut statement = self.connection.prepare(GROUPS)?;
+        let mut rows = statement.query(params![player])?;
+        while let Some(row) = rows.next()? {
+            let mode: &str = if row.get(0)? { "strict" } else if row.get(1)? { "difficult" } else { "normal" };
+            let final_set: Option<String> = row.get(2)?;
+            let acceptable_set: Option<String> = row.get(3)?;
+            let word_lists: String = word_lists_name(final_set.as_deref().zip(acceptable_set.as_deref()));
+            let max_attempts: usize = row.get::<_, Option<usize>>(5)?.unwrap_or(DEFAULT_MAX_ATTEMPTS);
+            totals.count(mode, word_lists, row.get(4)?, row.get(6)?, max_attempts, row.get(7)?);
+        }
+
+        let mut statement = self.connection.prepare(WORDS)?;
+        let mut rows = statement.query(params![player])?;
+        while let Some(row) = rows.next()? {
+            totals.used_words.insert(row.get(0)?, row.get(1)?);
+        }
+
+        (totals.current_streak, totals.max_streak) =
+            self.connection.query_row(STREAKS, params![player], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        Ok(totals)
+    }
+
+    fn query(&self, player: Option<&str>, filter: &Filter) -> rusqlite::Result<Vec<(usize, Record)>> {
+        let mut statement = self.connection.prepare(FIND)?;
+        let rows = statement.query_map(params![player, filter.won, filter.hard, filter.answer], Self::record)?;
+        let games: Vec<(i64, usize, Record)> = rows.collect::<rusqlite::Result<_>>()?;
+
+        let mut guesses: HashMap<i64, Vec<String>> = HashMap::new();
+        let mut statement = self.connection.prepare(
+            "SELECT game, word FROM guesses WHERE game IN (SELECT id FROM games WHERE player IS ?1) ORDER BY game, turn",
+        )?;
+        let mut rows = statement.query(params![player])?;
+        while let Some(row) = rows.next()? {
+            guesses.entry(row.get(0)?).or_default().push(row.get(1)?);
+        }
+
+        Ok(games
+            .into_iter()
+            .map(|(id, number, mut record)| {
+                record.guesses = guesses.remove(&id).unwrap_or_default();
+                (number, record)
+            })
+            .collect())
+    }
+}
+
+impl Store for SqliteStore {
+    fn games(&self, player: Option<&str>) -> Result<Vec<Record>, StoreError> {
+        let games: Vec<(usize, Record)> = self.checked(player, &Filter::default())?;
+        Ok(games.into_iter().map(|(_, game)| game).collect())
+    }
+
+    fn players(&self) -> Result<Vec<String>, StoreError> {
+        let players = || -> rusqlite::Result<Vec<String>> {
+            let mut statement = self.connection.prepare("SELECT name FROM players ORDER BY name")?;
+            let names = statement.query_map([], |row| row.get(0))?;
+            names.collect()
+        };
+        players().map_err(|source| self.error(source))
+    }
+
+    fn append(&mut self, player: Option<&str>, games: &[Record]) -> Result<(), StoreError> {
+        let mut append = || -> rusqlite::Result<()> {
+            let transaction: Transaction = self.connection.transaction()?;
+            if let Some(player) = player {
+                transaction.execute("INSERT OR IGNORE INTO players (name) VALUES (?1)", params![player])?;
+            }
+            for game in games {
+                Self::insert(&transaction, player, game)?;
+            }
+            transaction.commit()
+        };
+        append().map_err(|source| StoreError::Database { path: self.path.clone(), source })
+    }
+
+    fn replace(&mut self, player: Option<&str>, games: &[Record]) -> Result<(), StoreError> {
+        let mut replace = || -> rusqlite::Result<()> {
+            let transaction: Transaction = self.connection.transaction()?;
+            transaction.execute("DELETE FROM guesses WHERE game IN (SELECT id FROM games WHERE player IS ?1)", params![player])?;
+            transaction.execute("DELETE FROM games WHERE player IS ?1", params![player])?;
+            if let Some(player) = player {
+                transaction.execute("INSERT OR IGNORE INTO players (name) VALUES (?1)", params![player])?;
+            }
+            for game in games {
+                Self::insert(&transaction, player, game)?;
+            }
+            transaction.commit()
+        };
+        replace().map_err(|source| StoreError::Database { path: self.path.clone(), source })
+    }
+
+    fn find(&self, player: Option<&str>, filter: &Filter) -> Result<Vec<(usize, Record)>, StoreError> {
+        self.checked(player, filter)
+    }
+
+    fn totals(&self, player: Option<&str>) -> Result<Totals, StoreError> {
+        // counted by queries, but only once every game has been checked like `games` does
+        if let Some((number, problem)) = self.first_problem(player).map_err(|source| self.error(source))? {
+            return Err(self.impossible(player, number, problem));
+        }
+        self.count(player).map_err(|source| self.error(source))
+    }
+
+    fn played_on(&self, player: Option<&str>, date: NaiveDate) -> Result<bool, StoreError> {
+        self.connection
+            .query_row("SELECT EXISTS (SELECT 1 FROM games WHERE player IS ?1 AND date = ?2)", params![player, date], |row| row.get(0))
+            .map_err(|source| self.error(source))
+    }
+}
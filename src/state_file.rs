@@ -0,0 +1,86 @@
+use std::{
+    ffi::OsString,
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use fs2::FileExt;
+use serde::Serialize;
+
+/// What reading a state file found.
+pub enum Loaded<T, E> {
+    Missing,
+    Primary(T),
+    Backup(T), // the file itself is damaged, its backup could be read
+    Invalid(E), // why the file itself could not be read
+}
+
+/// The advisory lock of a state file, held until dropped.
+pub struct Lock {
+    _file: File,
+}
+
+// `path` with `suffix` appended to its file name
+fn sibling(path: &Path, suffix: &str) -> PathBuf {
+    let mut name: OsString = path.as_os_str().to_owned();
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+/// The copy of the previous state kept next to `path`.
+pub fn backup_path(path: &Path) -> PathBuf {
+    sibling(path, ".bak")
+}
+
+/// The name a damaged state file is kept under once a good state replaces it.
+pub fn damaged_path(path: &Path) -> PathBuf {
+    sibling(path, ".damaged")
+}
+
+/// Reads the state in `path` with `read`, falling back to its backup only when `is_damage` says the file is damaged;
+/// any other error, like a state too new to read, is returned as it is.
+pub fn read<T, E>(path: &Path, read: impl Fn(&Path) -> Result<T, E>, is_damage: impl Fn(&E) -> bool) -> Loaded<T, E> {
+    if !path.exists() {
+        return Loaded::Missing;
+    }
+    let error: E = match read(path) {
+        Ok(state) => return Loaded::Primary(state),
+        Err(error) => error,
+    };
+    if !is_damage(&error) {
+        return Loaded::Invalid(error);
+    }
+    match read(&backup_path(path)) {
+        Ok(state) => Loaded::Backup(state),
+        Err(_) => Loaded::Invalid(error),
+    }
+}
+
+/// Waits until no other session writes to `path`; the lock lives in a file of its own
+/// since the state file itself is replaced on every write.
+pub fn lock(path: &Path) -> io::Result<Lock> {
+    let file: File = OpenOptions::new().create(true).truncate(false).write(true).open(sibling(path, ".lock"))?;
+    file.lock_exclusive()?;
+    Ok(Lock { _file: file })
+}
+
+/// Moves the damaged file `path` out of the way, so that writing a new state does not lose it.
+pub fn set_aside(path: &Path) -> io::Result<PathBuf> {
+    let damaged: PathBuf = damaged_path(path);
+    fs::rename(path, &damaged)?;
+    Ok(damaged)
+}
+
+/// Replaces `path` by `state` in one step, so a crash leaves either the old or the new file.
+/// With `backup`, the old file is copied to the backup first.
+pub fn write<T: Serialize>(path: &Path, state: &T, backup: bool) -> io::Result<()> {
+    let temporary: PathBuf = sibling(path, ".tmp");
+    let mut file: File = File::create(&temporary)?;
+    file.write_all(serde_json::to_string_pretty(state)?.as_bytes())?;
+    file.sync_all()?;
+    if backup && path.exists() {
+        fs::copy(path, backup_path(path))?;
+    }
+    fs::rename(&temporary, path)
+}
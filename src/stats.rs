@@ -1,178 +1,496 @@
 use std::{
-    collections::HashMap,
-    path::PathBuf,
-    fs,
+    cell::OnceCell,
+    collections::{BTreeMap, HashMap},
+    path::Path,
 };
-use colored::Colorize;
 
+use chrono::{DateTime, Local, NaiveDate};
 use serde::{Deserialize, Serialize};
-use super::game::GuessWordStatus;
+use super::game::{get_status, Game, GuessWordStatus, DEFAULT_MAX_ATTEMPTS};
+use super::error::StoreError;
+use super::share::{share_text, Palette};
+use super::store::Store;
+use super::word_list::fingerprint;
 use rayon::prelude::*;
 
 type Counter = HashMap<String, usize>;
 
+/// One finished game as saved in the state file. Only the answer and the guesses are always there,
+/// older state files miss the rest.
 #[derive(Clone, Serialize, Deserialize)]
-struct Game {
-    answer: String,
-    guesses: Vec<String>,
+pub struct Record {
+    pub answer: String,
+    pub guesses: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_attempts: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date: Option<NaiveDate>, // the puzzle date of a daily game
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub puzzle: Option<usize>, // the puzzle number of a daily game
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub difficult: bool,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub strict: bool,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub random: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>, // random mode only
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub day: Option<usize>, // random mode only
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub word_lists: Option<WordLists>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub started_at: Option<DateTime<Local>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_secs: Option<u64>,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub hints_shown: usize,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub recommendations_shown: usize,
 }
 
-#[derive(Default, Serialize, Deserialize)]
-pub struct GameState {
-    total_rounds: Option<u32>,
-    games: Option<Vec<Game>>,
+impl Record {
+    pub fn is_win(&self) -> bool {
+        self.guesses.last() == Some(&self.answer)
+    }
+
+    /// What makes this record impossible to have been played, if anything.
+    pub fn problem(&self) -> Option<String> {
+        if !self.answer.chars().all(|c| c.is_ascii_uppercase()) {
+            return Some(format!("the answer {} is not a word", self.answer));
+        }
+        if self.guesses.is_empty() {
+            return Some("there are no guesses".to_string());
+        }
+        if let Some(guess) = self.guesses.iter().find(|guess| guess.len() != self.answer.len() || !guess.chars().all(|c| c.is_ascii_uppercase())) {
+            return Some(format!("the guess {} does not fit the answer {}", guess, self.answer));
+        }
+        if self.guesses[..self.guesses.len() - 1].contains(&self.answer) {
+            return Some("it goes on after the answer was found".to_string());
+        }
+        if self.guesses.len() > self.max_attempts() {
+            return Some(format!("there are {} guesses, more than the {} allowed", self.guesses.len(), self.max_attempts()));
+        }
+        None
+    }
+
+    /// States saved before the limit was configurable always had the default.
+    pub fn max_attempts(&self) -> usize {
+        self.max_attempts.unwrap_or(DEFAULT_MAX_ATTEMPTS)
+    }
+
+    /// `strict`, `difficult` or `normal`.
+    pub fn mode(&self) -> &'static str {
+        if self.strict {
+            "strict"
+        } else if self.difficult {
+            "difficult"
+        } else {
+            "normal"
+        }
+    }
 }
 
-// store information related to multi games
-pub struct Stats {
-    wins: i32,
-    fails: i32,
-    total_tries: i32,
-    used_words: Counter, // all guessed words
-    games: Vec<Game>, // single games in one GAME
-    state_path: Option<PathBuf>,
+/// Which word lists a game was played with: their files, `builtin` for the builtin ones, and a fingerprint of the words.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WordLists {
+    pub final_set: String,
+    pub acceptable_set: String,
+    pub fingerprint: String,
 }
 
-impl Stats{
-    pub fn new() -> Self {
-        Stats {
-            wins: 0,
-            fails: 0,
-            total_tries: 0,
+impl WordLists {
+    /// `None` for a file stands for the builtin list.
+    pub fn new(final_file: Option<&Path>, final_set: &[String], acceptable_file: Option<&Path>, acceptable_set: &[String]) -> Self {
+        let name = |file: Option<&Path>| file.map_or("builtin".to_string(), |path| path.display().to_string());
+        WordLists {
+            final_set: name(final_file),
+            acceptable_set: name(acceptable_file),
+            fingerprint: format!("{:016x}", fingerprint(final_set, acceptable_set)),
+        }
+    }
+}
+
+/// How the answer of a game was picked, recorded along with it.
+#[derive(Clone, Default)]
+pub struct Setup {
+    pub random: bool,
+    pub seed: Option<u64>,
+    pub day: Option<usize>,
+    pub word_lists: Option<WordLists>,
+}
+
+/// Games won and lost in one part of the recorded games.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Summary {
+    pub wins: usize,
+    pub fails: usize,
+    pub total_tries: usize, // guesses in the games won
+}
+
+impl Summary {
+    // `games` games all won or all lost with `tries` guesses each
+    fn count(&mut self, won: bool, tries: usize, games: usize) {
+        if won {
+            self.wins += games;
+            self.total_tries += tries * games;
+        } else {
+            self.fails += games;
+        }
+    }
+
+    pub fn games(&self) -> usize {
+        self.wins + self.fails
+    }
+
+    /// Share of games won, between 0 and 1.
+    pub fn success_rate(&self) -> f32 {
+        if self.wins == 0 { 0.0 } else { self.wins as f32 / self.games() as f32 }
+    }
+
+    /// Average number of guesses in the games won.
+    pub fn average_tries(&self) -> f64 {
+        if self.wins == 0 { 0.0 } else { self.total_tries as f64 / self.wins as f64 }
+    }
+}
+
+/// How many games were won in each number of guesses, and how many were lost.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Distribution {
+    pub wins: Vec<usize>, // games won in 1, 2, ... guesses, up to the largest limit played
+    pub fails: usize,
+}
+
+/// One player's line on the leaderboard.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Standing {
+    pub player: String,
+    pub summary: Summary,
+    pub current_streak: usize,
+    pub max_streak: usize,
+}
+
+/// The modes a game can be played in, in the order they are listed.
+pub const MODES: [&str; 3] = ["normal", "difficult", "strict"];
+
+/// Everything counted over the games of one player, kept up to date game by game.
+///
+/// ```
+/// use wordle::stats::Totals;
+///
+/// let store = wordle::store::open("tests/cases/07_02_load_state.before.json".as_ref(), None).unwrap();
+/// let totals: Totals = store.totals(None).unwrap();
+/// assert_eq!((totals.summary.wins, totals.summary.fails, totals.current_streak, totals.max_streak), (6, 0, 6, 6));
+/// assert_eq!(totals.distribution.wins, [0, 0, 0, 0, 5, 1]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Totals {
+    pub summary: Summary,
+    pub used_words: Counter, // all guessed words
+    pub distribution: Distribution,
+    pub current_streak: usize, // wins since the last lost game
+    pub max_streak: usize,
+    pub modes: [Summary; 3], // in the order of `MODES`
+    pub word_lists: BTreeMap<String, Summary>, // named `final / acceptable`, or `unrecorded`
+}
+
+impl Default for Totals {
+    fn default() -> Self {
+        Totals {
+            summary: Summary::default(),
             used_words: Counter::new(),
-            games: vec![],
-            state_path: None,
+            distribution: Distribution { wins: vec![0; DEFAULT_MAX_ATTEMPTS], fails: 0 },
+            current_streak: 0,
+            max_streak: 0,
+            modes: [Summary::default(); 3],
+            word_lists: BTreeMap::new(),
         }
     }
+}
 
-    // update one single game states
-    pub fn update(&mut self, guesses: &Vec<(String, GuessWordStatus)>, answer: String, is_win: bool) {
-        if is_win {
-            self.wins += 1;
-            self.total_tries += guesses.len() as i32;
+impl Totals {
+    pub fn of(games: &[Record]) -> Self {
+        let mut totals = Totals::default();
+        for game in games {
+            totals.add(game);
+        }
+        totals
+    }
+
+    /// Counts one more game, the latest.
+    pub fn add(&mut self, record: &Record) {
+        let word_lists: String = word_lists_name(record.word_lists.as_ref().map(|lists| (lists.final_set.as_str(), lists.acceptable_set.as_str())));
+        self.count(record.mode(), word_lists, record.is_win(), record.guesses.len(), record.max_attempts(), 1);
+        for word in &record.guesses {
+            *self.used_words.entry(word.to_string()).or_insert(0) += 1;
+        }
+        self.current_streak = if record.is_win() { self.current_streak + 1 } else { 0 };
+        self.max_streak = self.max_streak.max(self.current_streak);
+    }
+
+    /// Counts `games` games played in `mode` with `word_lists`, all won or all lost with `tries` guesses out of `max_attempts`;
+    /// neither their words nor the streaks are counted.
+    pub fn count(&mut self, mode: &str, word_lists: String, won: bool, tries: usize, max_attempts: usize, games: usize) {
+        self.summary.count(won, tries, games);
+        if let Some(i) = MODES.iter().position(|&known| known == mode) {
+            self.modes[i].count(won, tries, games);
+        }
+        self.word_lists.entry(word_lists).or_default().count(won, tries, games);
+        let longest: usize = self.distribution.wins.len().max(max_attempts).max(tries);
+        self.distribution.wins.resize(longest, 0);
+        if won {
+            self.distribution.wins[tries - 1] += games;
         } else {
-            self.fails += 1;
+            self.distribution.fails += games;
         }
-        let mut all_guess_words: Vec<String> = vec![];
-        for (word, _) in guesses {
-            self.count(word.to_owned());
-            all_guess_words.push(word.to_string());
+    }
+}
+
+/// How the word lists of a game are named in the totals: `final / acceptable`, or `unrecorded` for older games.
+pub fn word_lists_name(lists: Option<(&str, &str)>) -> String {
+    lists.map_or("unrecorded".to_string(), |(final_set, acceptable_set)| format!("{} / {}", final_set, acceptable_set))
+}
+
+fn is_false(value: &bool) -> bool {
+    !value
+}
+
+fn is_zero(value: &usize) -> bool {
+    *value == 0
+}
+
+/// Results of all games one player played, kept in a store. The totals are counted by the store when loading
+/// and kept up to date as games are added; the games themselves are only read when asked for.
+pub struct Stats {
+    totals: Totals, // of all games, stored or not
+    history: OnceCell<Vec<Record>>, // every game, oldest first, once read from the store
+    unsaved: Vec<Record>, // games added since loading, the store gets them on saving
+    store: Option<Box<dyn Store>>, // where the games are kept, if anywhere
+    player: Option<String>, // whose games these are, `None` for those played without a name
+}
+
+impl Default for Stats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Stats{
+    pub fn new() -> Self {
+        Stats {
+            totals: Totals::default(),
+            history: OnceCell::from(Vec::new()),
+            unsaved: Vec::new(),
+            store: None,
+            player: None,
         }
-        self.games.push(Game{
-            answer: answer.to_string(),
+    }
+
+    /// Adds a finished game, played with `setup`.
+    pub fn update(&mut self, game: &Game, setup: &Setup) {
+        let all_guess_words: Vec<String> = game.guesses.iter().map(|(word, _)| word.to_string()).collect();
+        self.add_record(Record {
+            answer: game.answer.to_string(),
             guesses: all_guess_words,
+            max_attempts: Some(game.max_attempts),
+            date: game.daily.map(|daily| daily.date),
+            puzzle: game.daily.map(|daily| daily.number),
+            difficult: game.is_difficult(),
+            strict: game.strict,
+            random: setup.random,
+            seed: setup.seed,
+            day: setup.day,
+            word_lists: setup.word_lists.clone(),
+            started_at: Some(game.started_at),
+            duration_secs: Some((Local::now() - game.started_at).num_seconds().max(0) as u64),
+            hints_shown: game.hints_shown,
+            recommendations_shown: game.recommendations_shown,
         })
     }
 
-    fn count(&mut self, word: String) {
-        let entry = self.used_words.entry(word).or_insert(0);
-        *entry += 1;
+    /// Adds a game recorded elsewhere, kept on the next save like one just played.
+    pub fn add_record(&mut self, record: Record) {
+        self.totals.add(&record);
+        if let Some(history) = self.history.get_mut() {
+            history.push(record.clone());
+        }
+        self.unsaved.push(record);
+    }
+
+    /// Games recorded so far.
+    pub fn get_games_count(&self) -> usize {
+        self.totals.summary.games()
     }
 
-    pub fn get_success_rate(&self) -> f32{
-        if self.wins == 0 {
-            0.0
-        }else {
-            let tries = self.wins + self.fails;
-            self.wins as f32 / tries as f32
+    /// Every recorded game, oldest first, read from the store the first time.
+    pub fn get_records(&self) -> Result<&[Record], StoreError> {
+        if let Some(history) = self.history.get() {
+            return Ok(history);
         }
+        let mut games: Vec<Record> = match &self.store {
+            Some(store) => store.games(self.player.as_deref())?,
+            None => Vec::new(),
+        };
+        games.extend(self.unsaved.iter().cloned());
+        Ok(self.history.get_or_init(|| games))
     }
-    
-    pub fn get_wins(&self) -> i32 {
-        self.wins
+
+    /// The `index`th recorded game, counting from 0.
+    pub fn get_record(&self, index: usize) -> Result<Option<&Record>, StoreError> {
+        Ok(self.get_records()?.get(index))
     }
 
-    pub fn get_fails(&self) -> i32 {
-        self.fails
+    /// The result block of the `index`th recorded game, counting from 0; `None` past the last one.
+    pub fn share(&self, index: usize, palette: Palette) -> Result<Option<String>, StoreError> {
+        let Some(record) = self.get_record(index)? else {
+            return Ok(None);
+        };
+        let title: String = record.puzzle.map_or("Wordle".to_string(), |number| format!("Wordle {}", number));
+        let rows: Vec<GuessWordStatus> = record.guesses.iter().map(|word| get_status(word, &record.answer)).collect();
+        Ok(Some(share_text(&title, &rows, record.is_win(), record.max_attempts(), record.difficult || record.strict, palette)))
     }
 
-    pub fn get_frequent_words(&self) -> Vec<(&String, &usize)>{
-        let mut words: Vec<(&String, &usize)> = self.used_words.iter().collect();
-        words.par_sort_by(|a: &(&String, &usize), b: &(&String, &usize)| b.1.partial_cmp(a.1).unwrap().then_with(|| a.0.cmp(b.0)));
-        words
+    /// Whether the daily puzzle of `date` has been finished already.
+    pub fn played_on(&self, date: NaiveDate) -> Result<bool, StoreError> {
+        if self.unsaved.iter().any(|game| game.date == Some(date)) {
+            return Ok(true);
+        }
+        match &self.store {
+            Some(store) => store.played_on(self.player.as_deref(), date),
+            None => Ok(false),
+        }
     }
 
-    pub fn print_stats(&self, is_tty: bool) {
-        let words: Vec<(&String, &usize)> = self.get_frequent_words();
+    /// Wins since the last lost game.
+    pub fn get_current_streak(&self) -> usize {
+        self.totals.current_streak
+    }
 
-        if is_tty {
-            println!("You winned {} games, lost {} games!", self.wins.to_string().blue(), self.fails.to_string().blue());
-            println!("Your chance of winning is {:.2}", self.get_success_rate().to_string().blue());
-            println!("The words that you use most frequently are:");
-    
-            for (_, (word, count)) in words.iter().take(5).enumerate() {
-                print!("{} {} ", word.to_string().green(), count.to_string().blue());
-            }
-            println!();
-        } else {
-            print!("{} {} {:.2}", self.wins, self.fails, self.get_average_tries());
-            println!();            
-            for (i, &(word, count)) in words.iter().enumerate().take(5) {
-                print!("{} {}", word, count);
-                if i < 4 && i < words.len() - 1 {
-                    print!(" ");
+    /// The longest run of wins.
+    pub fn get_max_streak(&self) -> usize {
+        self.totals.max_streak
+    }
+
+    /// Every player with a profile in the store, best first:
+    /// more games won in proportion, then fewer guesses on average, then the longer streak.
+    ///
+    /// ```
+    /// use wordle::stats::Stats;
+    ///
+    /// let store = wordle::store::open("tests/data/20_profiles.json".as_ref(), None).unwrap();
+    /// let stats = Stats::load(Some(store), Some("Ada")).unwrap();
+    /// let players: Vec<String> = stats.get_leaderboard().into_iter().map(|standing| standing.player).collect();
+    /// assert_eq!(players, ["Ada", "Grace", "Alan"]);
+    /// ```
+    pub fn get_leaderboard(&self) -> Vec<Standing> {
+        let mut profiles: BTreeMap<String, Totals> = BTreeMap::new();
+        if let Some(store) = &self.store {
+            for player in store.players().unwrap_or_default() {
+                if let Ok(totals) = store.totals(Some(&player)) {
+                    profiles.insert(player, totals);
                 }
             }
         }
-        
-        println!();
-    }
-
-    pub fn get_average_tries(&self) -> f64{
-        if self.wins == 0 {
-            return 0.0;
-        } 
-        self.total_tries as f64 / self.wins as f64
-    }
-
-    pub fn load(state_path: &Option<PathBuf>) -> Option<Self> {
-        if state_path.is_some() { 
-            let mut stats = Self::new();
-            stats.state_path = state_path.clone();
-
-            // file exist
-            if PathBuf::from(state_path.as_ref().unwrap()).exists() {
-                // load json to stuct GameState
-                if let Ok(state) = serde_json::from_str::<GameState>(
-                    fs::read_to_string(state_path.as_ref().unwrap())
-                        .unwrap()
-                        .as_str(),
-                ) {
-                    // json is not empty, load and count
-                    if let Some(games) = state.games {
-                        for game in games {
-                            stats.games.push(game.clone());
-                            if game.guesses.last()? == &game.answer {
-                                stats.wins += 1;
-                                stats.total_tries += game.guesses.len() as i32;
-                            } else {
-                                stats.fails += 1;
-                            }
-                            for word in game.guesses {
-                                Self::count(&mut stats, word);
-                            }
-                        }
-                    }
-                    Some(stats)
-                } else { // INVALID
-                    None
-                }
-            } else { // INVALID
-                Some(stats)
-            }
-        } else { // file path empty
-            Some(Self::new())
+        if let Some(player) = &self.player {
+            profiles.insert(player.clone(), self.totals.clone()); // newer than the store until saved
         }
+        let mut standings: Vec<Standing> = profiles
+            .into_iter()
+            .map(|(player, totals)| Standing {
+                summary: totals.summary,
+                current_streak: totals.current_streak,
+                max_streak: totals.max_streak,
+                player,
+            })
+            .collect();
+        standings.sort_by(|a, b| {
+            b.summary.success_rate().total_cmp(&a.summary.success_rate())
+                .then(a.summary.average_tries().total_cmp(&b.summary.average_tries()))
+                .then(b.max_streak.cmp(&a.max_streak))
+        });
+        standings
     }
 
-    // after write information into struct
-    pub fn save(&mut self) {
-        let state = GameState {
-            total_rounds: Some((self.wins + self.fails) as u32),
-            games: Some(self.games.clone()),
-        };
-        if let Some(path) = &self.state_path {
-            fs::write(path, serde_json::to_string_pretty(&state).unwrap()).unwrap();
+    /// The guess distribution of all recorded games, at least as long as the default limit.
+    ///
+    /// ```
+    /// use wordle::stats::Stats;
+    ///
+    /// let store = wordle::store::open("tests/cases/07_02_load_state.before.json".as_ref(), None).unwrap();
+    /// let stats = Stats::load(Some(store), None).unwrap();
+    /// let distribution = stats.get_distribution();
+    /// assert_eq!((distribution.wins, distribution.fails), (vec![0, 0, 0, 0, 5, 1], 0));
+    /// assert_eq!((stats.get_current_streak(), stats.get_max_streak()), (6, 6));
+    /// ```
+    pub fn get_distribution(&self) -> Distribution {
+        self.totals.distribution.clone()
+    }
+
+    /// Results of normal, difficult and strict games, leaving out modes never played.
+    pub fn get_mode_breakdown(&self) -> Vec<(&'static str, Summary)> {
+        MODES.into_iter().zip(self.totals.modes).filter(|(_, summary)| summary.games() > 0).collect()
+    }
+
+    /// Results for each pair of word lists, named `final / acceptable`; older games did not record theirs.
+    pub fn get_word_list_breakdown(&self) -> Vec<(String, Summary)> {
+        self.totals.word_lists.iter().map(|(name, summary)| (name.clone(), *summary)).collect()
+    }
+
+    /// Share of games won, between 0 and 1.
+    pub fn get_success_rate(&self) -> f32 {
+        self.totals.summary.success_rate()
+    }
+
+    pub fn get_wins(&self) -> usize {
+        self.totals.summary.wins
+    }
+
+    pub fn get_fails(&self) -> usize {
+        self.totals.summary.fails
+    }
+
+    /// Every guessed word with its count, most frequent first and ties in alphabetical order.
+    pub fn get_frequent_words(&self) -> Vec<(&String, &usize)>{
+        let mut words: Vec<(&String, &usize)> = self.totals.used_words.iter().collect();
+        words.par_sort_by(|a: &(&String, &usize), b: &(&String, &usize)| b.1.partial_cmp(a.1).unwrap().then_with(|| a.0.cmp(b.0)));
+        words
+    }
+
+    /// Average number of guesses in the games won.
+    pub fn get_average_tries(&self) -> f64 {
+        self.totals.summary.average_tries()
+    }
+
+    /// Loads what the games `player` saved in `store` add up to, those played without a name for `None`.
+    pub fn load(store: Option<Box<dyn Store>>, player: Option<&str>) -> Result<Self, StoreError> {
+        let mut stats = Self::new();
+        stats.player = player.map(str::to_string);
+        if let Some(store) = &store {
+            stats.totals = store.totals(player)?;
+            stats.history = OnceCell::new();
         }
+        stats.store = store;
+        Ok(stats)
+    }
+
+    /// Whether the store was damaged and its backup loaded instead.
+    pub fn is_recovered(&self) -> bool {
+        self.store.as_ref().is_some_and(|store| store.is_recovered())
+    }
+
+    /// Where the games are kept, if anywhere.
+    pub fn store(&self) -> Option<&dyn Store> {
+        self.store.as_deref()
     }
-}
\ No newline at end of file
+
+    /// Adds the games played since loading to the store, if there is one, after whatever other sessions added meanwhile.
+    /// The totals only count the games of this session on top of those loaded.
+    pub fn save(&mut self) -> Result<(), StoreError> {
+        let Some(store) = &mut self.store else {
+            return Ok(());
+        };
+        store.append(self.player.as_deref(), &self.unsaved)?;
+        self.unsaved.clear();
+        Ok(())
+    }
+}
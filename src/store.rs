@@ -0,0 +1,131 @@
+use std::{fmt, path::Path, str::FromStr};
+
+use chrono::NaiveDate;
+
+use serde::Deserialize;
+
+use crate::error::StoreError;
+use crate::json_store::JsonStore;
+use crate::sqlite_store::SqliteStore;
+use crate::stats::{Record, Totals};
+
+/// How the history of games is kept.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Backend {
+    /// One JSON state file, rewritten as a whole on every save.
+    Json,
+    /// An SQLite database, which every game is added to.
+    Sqlite,
+}
+
+impl Backend {
+    /// SQLite for files ending in `.db`, `.sqlite` or `.sqlite3`, JSON for anything else.
+    pub fn of(path: &Path) -> Self {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("db" | "sqlite" | "sqlite3") => Self::Sqlite,
+            _ => Self::Json,
+        }
+    }
+}
+
+impl FromStr for Backend {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name.to_lowercase().as_str() {
+            "json" => Ok(Self::Json),
+            "sqlite" => Ok(Self::Sqlite),
+            _ => Err(format!("unknown store \"{}\", expected json or sqlite", name)),
+        }
+    }
+}
+
+impl fmt::Display for Backend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Json => write!(f, "json"),
+            Self::Sqlite => write!(f, "sqlite"),
+        }
+    }
+}
+
+/// Which games to look for; `None` matches either way.
+#[derive(Debug, Clone, Default)]
+pub struct Filter {
+    pub won: Option<bool>,
+    pub hard: Option<bool>, // difficult or strict
+    pub answer: Option<String>,
+}
+
+impl Filter {
+    pub fn matches(&self, record: &Record) -> bool {
+        self.won.is_none_or(|won| record.is_win() == won)
+            && self.hard.is_none_or(|hard| (record.difficult || record.strict) == hard)
+            && self.answer.as_ref().is_none_or(|answer| &record.answer == answer)
+    }
+}
+
+/// Somewhere the games of every player are kept; `None` for a player stands for the games played without a name.
+pub trait Store {
+    /// The games of `player`, oldest first.
+    fn games(&self, player: Option<&str>) -> Result<Vec<Record>, StoreError>;
+
+    /// Everyone with a profile, in alphabetical order.
+    fn players(&self) -> Result<Vec<String>, StoreError>;
+
+    /// Adds `games` after those of `player`, including any that other sessions added meanwhile.
+    fn append(&mut self, player: Option<&str>, games: &[Record]) -> Result<(), StoreError>;
+
+    /// Makes `games` all the games of `player`, in that order.
+    fn replace(&mut self, player: Option<&str>, games: &[Record]) -> Result<(), StoreError>;
+
+    /// The games of `player` that `filter` matches, each with its number among all their games, counting from 1.
+    fn find(&self, player: Option<&str>, filter: &Filter) -> Result<Vec<(usize, Record)>, StoreError> {
+        let games = self.games(player)?.into_iter().enumerate().map(|(i, game)| (i + 1, game));
+        Ok(games.filter(|(_, game)| filter.matches(game)).collect())
+    }
+
+    /// What the games of `player` add up to.
+    fn totals(&self, player: Option<&str>) -> Result<Totals, StoreError> {
+        Ok(Totals::of(&self.games(player)?))
+    }
+
+    /// Whether `player` finished the daily puzzle of `date`.
+    fn played_on(&self, player: Option<&str>, date: NaiveDate) -> Result<bool, StoreError> {
+        Ok(self.games(player)?.iter().any(|game| game.date == Some(date)))
+    }
+
+    /// Whether the history was damaged and read from a backup.
+    fn is_recovered(&self) -> bool {
+        false
+    }
+}
+
+/// Opens the history in `path` kept by `backend`, told by the extension of `path` when `None`.
+pub fn open(path: &Path, backend: Option<Backend>) -> Result<Box<dyn Store>, StoreError> {
+    match backend.unwrap_or_else(|| Backend::of(path)) {
+        Backend::Json => Ok(Box::new(JsonStore::open(path)?)),
+        Backend::Sqlite => Ok(Box::new(SqliteStore::open(path)?)),
+    }
+}
+
+/// Opens the history in `path` like `open`, only to read it: a database is left exactly as it is.
+///
+/// ```
+/// use wordle::store::{self, Store};
+///
+/// let path = std::env::temp_dir().join("wordle_read_only.db");
+/// let _ = std::fs::remove_file(&path);
+/// let games = store::open("tests/data/20_profiles.json".as_ref(), None).unwrap().games(Some("Ada")).unwrap();
+/// store::open(&path, None).unwrap().append(Some("Ada"), &games).unwrap();
+/// let mut source = store::open_read_only(&path, None).unwrap();
+/// assert_eq!(source.games(Some("Ada")).unwrap().len(), 3);
+/// assert!(source.append(Some("Ada"), &games).is_err());
+/// ```
+pub fn open_read_only(path: &Path, backend: Option<Backend>) -> Result<Box<dyn Store>, StoreError> {
+    match backend.unwrap_or_else(|| Backend::of(path)) {
+        Backend::Json => Ok(Box::new(JsonStore::open(path)?)),
+        Backend::Sqlite => Ok(Box::new(SqliteStore::open_read_only(path)?)),
+    }
+}
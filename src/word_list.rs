@@ -0,0 +1,89 @@
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{self, BufRead, BufReader},
+    path::Path,
+};
+
+use super::builtin_words;
+use super::error::WordListError;
+
+/// Stands in for a file name when the builtin lists are reported in errors.
+pub const BUILTIN_PATH: &str = "builtin_words.rs";
+
+/// Reads one word per line, uppercased and validated against `word_length`.
+pub fn load(path: &Path, word_length: usize) -> Result<Vec<String>, WordListError> {
+    let file: File = File::open(path).map_err(|source| match source.kind() {
+        io::ErrorKind::NotFound => WordListError::MissingFile { path: path.to_path_buf() },
+        _ => WordListError::Unreadable { path: path.to_path_buf(), source },
+    })?;
+    let mut words: Vec<String> = Vec::new();
+    let mut seen: HashMap<String, usize> = HashMap::new(); // word -> first line
+    let reader: BufReader<File> = BufReader::new(file); // read line by line
+    for (index, line) in reader.lines().enumerate() {
+        let line_number: usize = index + 1;
+        let line: String = line.map_err(|source| WordListError::Unreadable { path: path.to_path_buf(), source })?;
+        // check if there are multiple words in a line
+        if line.split_whitespace().count() > 1 {
+            return Err(WordListError::MultipleWords { path: path.to_path_buf(), line: line_number });
+        }
+        let word: String = line.trim().to_uppercase();
+        if word.len() != word_length {
+            return Err(WordListError::WrongLength { path: path.to_path_buf(), line: line_number, word, expected: word_length });
+        }
+        if !word.chars().all(|c| c.is_ascii_alphabetic()) {
+            return Err(WordListError::NonAlphabetic { path: path.to_path_buf(), line: line_number, word });
+        }
+        if let Some(&first_line) = seen.get(&word) {
+            return Err(WordListError::Duplicated { path: path.to_path_buf(), line: line_number, word, first_line });
+        }
+        seen.insert(word.clone(), line_number);
+        words.push(word);
+    }
+    Ok(words)
+}
+
+/// Checks that every answer can also be guessed; `acceptable` has to be sorted.
+pub fn check_subset(final_set: &[String], final_path: &Path, acceptable: &[String], acceptable_path: &Path) -> Result<(), WordListError> {
+    for (index, word) in final_set.iter().enumerate() {
+        if acceptable.binary_search(word).is_err() {
+            return Err(WordListError::NotSubset {
+                path: final_path.to_path_buf(),
+                line: index + 1,
+                word: word.to_string(),
+                acceptable_path: acceptable_path.to_path_buf(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// FNV-1a over both lists, stable across builds unlike `DefaultHasher`.
+pub fn fingerprint(first: &[String], second: &[String]) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for word in first.iter().chain([&String::new()]).chain(second) {
+        for byte in word.bytes().chain([b'\n']) {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+    }
+    hash
+}
+
+/// The builtin answers of the given length; only five-letter words are built in.
+pub fn builtin_final(word_length: usize) -> Vec<String> {
+    builtin_words::FINAL
+        .iter()
+        .filter(|s| s.len() == word_length)
+        .map(|s| s.to_uppercase())
+        .collect()
+}
+
+/// The builtin acceptable guesses of the given length, sorted.
+pub fn builtin_acceptable(word_length: usize) -> Vec<String> {
+    builtin_words::ACCEPTABLE
+        .iter()
+        .filter(|s| s.len() == word_length)
+        .map(|s| s.to_uppercase())
+        .collect()
+}
@@ -112,8 +112,13 @@ impl TestCase {
         self.run_and_compare_result();
 
         // load state and compare with answer
-        let run_state: serde_json::Value =
+        let mut run_state: serde_json::Value =
             serde_json::from_reader(BufReader::new(File::open(&run_state_file).unwrap())).unwrap();
+        // when and how long a game was played differs from run to run
+        strip_timing(&mut run_state);
+        if let Some(players) = run_state.get_mut("players").and_then(|players| players.as_object_mut()) {
+            players.values_mut().for_each(strip_timing);
+        }
         let answer_state: serde_json::Value =
             serde_json::from_reader(BufReader::new(File::open(&after_state_file).unwrap()))
                 .unwrap();
@@ -133,3 +138,12 @@ impl TestCase {
         );
     }
 }
+
+fn strip_timing(state: &mut serde_json::Value) {
+    if let Some(games) = state.get_mut("games").and_then(|games| games.as_array_mut()) {
+        for game in games.iter_mut().filter_map(|game| game.as_object_mut()) {
+            game.remove("started_at");
+            game.remove("duration_secs");
+        }
+    }
+}
@@ -0,0 +1,215 @@
+use ntest::timeout;
+
+mod common;
+use common::TestCase;
+
+#[test]
+#[timeout(2000)]
+fn test_09_word_length() {
+    // four-letter words from custom word lists
+    TestCase::read("09_01_word_length").run_and_compare_result();
+    // word lists that do not match the requested length
+    TestCase::read("09_02_word_length_mismatch").run_and_expect_exit();
+}
+
+#[test]
+#[timeout(1000)]
+fn test_10_max_attempts() {
+    // the game is lost once the configured number of guesses is used up
+    TestCase::read("10_01_max_attempts").run_and_compare_result();
+}
+
+#[test]
+#[timeout(3000)]
+fn test_11_subcommands() {
+    // the recommender solves a given answer from a given start word
+    TestCase::read("11_01_solve").run_and_compare_result();
+    // statistics of a state file without playing
+    TestCase::read("11_02_stats").run_and_compare_result();
+    // options that only make sense in random mode
+    TestCase::read("11_03_day_without_random").run_and_expect_exit();
+    // the panel of a terminal on request, with a lost game between two runs of wins
+    TestCase::read("11_04_stats_detailed").run_and_compare_result();
+}
+
+#[test]
+#[timeout(1000)]
+fn test_12_load_errors() {
+    // a word list with the same word twice
+    TestCase::read("12_01_duplicated_word_list").run_and_expect_exit();
+    // a config file that is not valid JSON
+    TestCase::read("12_02_invalid_config").run_and_expect_exit();
+}
+
+#[test]
+#[timeout(1000)]
+fn test_13_end_of_input() {
+    // running out of input ends the session instead of spinning on empty guesses
+    TestCase::read("13_01_end_of_input").run_and_compare_result();
+}
+
+#[test]
+#[timeout(2000)]
+fn test_14_entropy_solver() {
+    // guesses that maximise the information of the feedback
+    TestCase::read("14_01_entropy_solve").run_and_compare_result();
+}
+
+#[test]
+#[timeout(2000)]
+fn test_15_bench() {
+    // every answer against every first guess, through the pattern matrix
+    TestCase::read("15_01_bench").run_and_compare_result();
+    // worst case and failures of every strategy side by side
+    TestCase::read("15_02_bench_compare").run_and_compare_result();
+    // probes that break the difficult mode rule are never recommended
+    TestCase::read("15_03_bench_difficult").run_and_compare_result();
+}
+
+#[test]
+#[timeout(1000)]
+fn test_16_strict_mode() {
+    // guesses that difficult mode accepts but that contradict earlier feedback
+    TestCase::read("16_01_strict_mode").run_and_compare_result();
+}
+
+#[test]
+#[timeout(1000)]
+fn test_17_daily() {
+    // the puzzle of a date is numbered from the epoch and recorded with its date
+    TestCase::read("17_01_daily").run_and_compare_game_state();
+    // a date that has been played already
+    TestCase::read("17_02_daily_played").run_and_expect_exit();
+}
+
+#[test]
+#[timeout(1000)]
+fn test_18_share() {
+    // a result block after the game, starred in difficult mode
+    TestCase::read("18_01_share").run_and_compare_result();
+    // the last game of a state file in the high contrast palette
+    TestCase::read("18_02_share_history").run_and_compare_result();
+}
+
+#[test]
+#[timeout(1000)]
+fn test_19_replay() {
+    // a game from a state file saved before records had any details
+    TestCase::read("19_01_replay_old_state").run_and_compare_result();
+    // a game past the end of the history
+    TestCase::read("19_02_replay_missing").run_and_expect_exit();
+}
+
+#[test]
+#[timeout(1000)]
+fn test_20_player_profiles() {
+    // a named player's game goes to their profile, the others stay as they were
+    TestCase::read("20_01_player_profile").run_and_compare_game_state();
+    // players by share of wins, then fewer guesses
+    TestCase::read("20_02_leaderboard").run_and_compare_result();
+    // a name is looked up the way play saved it, without the spaces around it
+    TestCase::read("20_03_padded_player").run_and_compare_result();
+}
+
+#[test]
+#[timeout(1000)]
+fn test_21_state_backup() {
+    // a state file cut short by a crash is read from its backup
+    TestCase::read("21_01_recover_backup").run_and_compare_result();
+    // a file of a newer version is left alone rather than replaced by its backup
+    for extension in ["json", "json.bak"] {
+        std::fs::copy(format!("tests/data/21_newer.{}", extension), format!("tests/cases/21_newer.run.{}", extension)).unwrap();
+    }
+    TestCase::read("21_02_newer_schema").run_and_expect_exit();
+    assert_eq!(
+        std::fs::read_to_string("tests/cases/21_newer.run.json").unwrap(),
+        std::fs::read_to_string("tests/data/21_newer.json").unwrap()
+    );
+    // and so is a file with a game that cannot have been played, its other games are not lost to the backup
+    for extension in ["json", "json.bak"] {
+        std::fs::copy(format!("tests/data/21_impossible.{}", extension), format!("tests/cases/21_impossible.run.{}", extension)).unwrap();
+    }
+    TestCase::read("21_03_impossible_game").run_and_expect_exit();
+    assert_eq!(
+        std::fs::read_to_string("tests/cases/21_impossible.run.json").unwrap(),
+        std::fs::read_to_string("tests/data/21_impossible.json").unwrap()
+    );
+}
+
+#[test]
+#[timeout(1000)]
+fn test_22_state_schema() {
+    // an unversioned file with a player gains the version
+    TestCase::read("22_01_migrate").run_and_compare_game_state();
+    // an unversioned file that was never saved to gains a game list, every change reported
+    TestCase::read("22_04_migrate_empty").run_and_compare_game_state();
+    // games that cannot have been played
+    TestCase::read("22_02_validate").run_and_expect_exit();
+    // and refused when loading rather than replayed
+    TestCase::read("22_03_replay_impossible").run_and_expect_exit();
+}
+
+#[test]
+#[timeout(2000)]
+fn test_23_sqlite_store() {
+    // games played into a fresh database by its extension, then queried
+    let _ = std::fs::remove_file("tests/cases/23_history.run.db");
+    TestCase::read("23_01_sqlite_win").run_and_compare_result();
+    TestCase::read("23_02_sqlite_loss").run_and_compare_result();
+    TestCase::read("23_03_history_query").run_and_compare_result();
+    TestCase::read("23_04_sqlite_stats").run_and_compare_result();
+}
+
+#[test]
+#[timeout(1000)]
+fn test_24_stats_export() {
+    // one row per game as CSV
+    TestCase::read("24_01_export_csv").run_and_compare_result();
+    // one row per guess with its feedback as JSON Lines
+    TestCase::read("24_02_export_guesses").run_and_compare_result();
+}
+
+#[test]
+#[timeout(1000)]
+fn test_25_stats_merge() {
+    // a game on both machines is kept once, clashing ones are reported
+    let _ = std::fs::remove_file("tests/cases/25_merge.run.json");
+    TestCase::read("25_01_merge").run_and_compare_result();
+    // the merged file holds the union
+    TestCase::read("25_02_merged_stats").run_and_compare_result();
+    // older games merged into a newer file go first, so the streaks run across both
+    std::fs::copy("tests/data/25_merge_newer.json", "tests/cases/25_merge_newer.run.json").unwrap();
+    TestCase::read("25_03_merge_older").run_and_compare_result();
+}
+
+#[test]
+#[timeout(2000)]
+fn test_26_analysis() {
+    // every stored game of a player, then the averages
+    TestCase::read("26_01_analyse_history").run_and_compare_result();
+    // right after a game
+    TestCase::read("26_02_analyse_after_game").run_and_compare_result();
+    // games of another length than the word lists are skipped, or refused when asked for
+    TestCase::read("26_03_analyse_mixed_lengths").run_and_compare_result();
+    TestCase::read("26_04_analyse_other_length").run_and_expect_exit();
+}
+
+#[test]
+#[timeout(2000)]
+fn test_27_assist() {
+    // feedback typed in from another game, with mistakes and an undo
+    TestCase::read("27_01_assist").run_and_compare_result();
+    // a guess hard mode would not have allowed is refused
+    TestCase::read("27_02_assist_difficult").run_and_compare_result();
+}
+
+#[test]
+#[timeout(2000)]
+fn test_28_reverse() {
+    // the answers and guesses behind a result block
+    TestCase::read("28_01_reverse").run_and_compare_result();
+    // only the guesses that lead to one answer
+    TestCase::read("28_02_reverse_answer").run_and_compare_result();
+    // a row after the game was won
+    TestCase::read("28_03_reverse_invalid").run_and_expect_exit();
+}